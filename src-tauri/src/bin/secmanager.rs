@@ -0,0 +1,543 @@
+//! Headless CLI for scripts and CI. Uses the same backend functions, caches and
+//! settings as the desktop app, so no AWS CLI is required.
+
+use base64::Engine as _;
+use clap::{Parser, Subcommand};
+use secretsmanager_lib::commands::aws::{self, SecretWrite};
+use secretsmanager_lib::commands::bundle::{self, ImportAction};
+use secretsmanager_lib::commands::certificates;
+use secretsmanager_lib::commands::config::{self, SecretContent};
+use secretsmanager_lib::commands::guard::{self, ProtectedAction};
+use secretsmanager_lib::commands::lint;
+use secretsmanager_lib::commands::schema;
+use secretsmanager_lib::helper::json_diff::{self, ChangeKind};
+use secretsmanager_lib::helper::lint::Severity;
+use secretsmanager_lib::helper::{aws_helper, cert, json_path};
+use serde_json::Value;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(
+    name = "secmanager",
+    version,
+    about = "Manage AWS Secrets Manager secrets from the command line"
+)]
+struct Cli {
+    /// AWS profile; defaults to the profile selected in the app
+    #[arg(long, short, global = true)]
+    profile: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List secret names (from the app cache unless --refresh is given)
+    List {
+        #[arg(long)]
+        prefix: Option<String>,
+        /// Fetch from AWS and update the cache
+        #[arg(long)]
+        refresh: bool,
+        /// List secrets scheduled for deletion instead
+        #[arg(long)]
+        deleted: bool,
+    },
+    /// Print a secret value (binary values as base64 unless --out is given)
+    Get {
+        secret_id: String,
+        /// JSON pointer or dot path of a single key
+        #[arg(long)]
+        key: Option<String>,
+        /// Write the raw value to a file (owner-only, 0600) instead of stdout
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Create or update a secret from --value, --file or stdin
+    Put {
+        secret_id: String,
+        #[arg(long, conflicts_with = "file")]
+        value: Option<String>,
+        #[arg(long)]
+        file: Option<PathBuf>,
+        /// Store the input bytes as a binary secret
+        #[arg(long)]
+        binary: bool,
+        #[arg(long)]
+        description: Option<String>,
+        /// Fail if AWSCURRENT is no longer this version
+        #[arg(long)]
+        expected_version_id: Option<String>,
+        /// Secret name typed again to confirm a write to a protected profile
+        #[arg(long)]
+        confirm: Option<String>,
+    },
+    /// Schedule a secret for deletion (default recovery window)
+    Delete {
+        secret_id: String,
+        /// Secret name typed again to confirm a write to a protected profile
+        #[arg(long)]
+        confirm: Option<String>,
+    },
+    /// Cancel a scheduled deletion
+    Restore {
+        secret_id: String,
+        /// Secret name typed again to confirm a write to a protected profile
+        #[arg(long)]
+        confirm: Option<String>,
+    },
+    /// Compare a secret with a local file or another secret; exits 1 when they differ
+    Diff {
+        secret_id: String,
+        #[arg(long, conflicts_with = "other", required_unless_present = "other")]
+        file: Option<PathBuf>,
+        #[arg(long)]
+        other: Option<String>,
+        /// Profile of --other; defaults to --profile
+        #[arg(long)]
+        other_profile: Option<String>,
+        /// Print changed values, not only key paths
+        #[arg(long)]
+        show_values: bool,
+    },
+    /// Export secrets to a JSON bundle
+    Export {
+        /// Explicit secret ids; when empty, every secret under --prefix
+        secret_ids: Vec<String>,
+        #[arg(long)]
+        prefix: Option<String>,
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Import a JSON bundle (conflicts are always skipped)
+    Import {
+        path: PathBuf,
+        /// Only print the plan
+        #[arg(long)]
+        dry_run: bool,
+        /// Limit the import to these entries
+        #[arg(long = "name")]
+        names: Vec<String>,
+        /// Profile name typed again to confirm an import into a protected profile
+        #[arg(long)]
+        confirm: Option<String>,
+    },
+    /// Report empty, placeholder, weak or reused values, unencrypted keys and expired
+    /// certificates; exits 1 when any error-level finding is reported
+    Lint {
+        /// Explicit secret ids; when empty, every secret under --prefix
+        secret_ids: Vec<String>,
+        #[arg(long)]
+        prefix: Option<String>,
+    },
+    /// List certificates stored under a prefix by expiry date; exits 1 when any
+    /// expires within --days
+    Certs {
+        #[arg(long)]
+        prefix: Option<String>,
+        #[arg(long, default_value_t = cert::EXPIRY_WARNING_DAYS)]
+        days: i64,
+    },
+    /// Check secret values against their JSON Schemas; exits 1 when any fail
+    Validate {
+        #[arg(long, default_value = "")]
+        prefix: String,
+    },
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let profile = cli.profile.or_else(config::load_default_profile);
+    match run(profile, cli.command).await {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+async fn run(profile: Option<String>, command: Command) -> Result<ExitCode, String> {
+    match command {
+        Command::List {
+            prefix,
+            refresh,
+            deleted,
+        } => {
+            let names = if deleted {
+                aws::list_deleted_secrets(profile).await?
+            } else {
+                list_names(profile, refresh).await?
+            };
+            let prefix = prefix.unwrap_or_default();
+            for name in names.iter().filter(|n| n.starts_with(&prefix)) {
+                println!("{name}");
+            }
+        }
+        Command::Get {
+            secret_id,
+            key,
+            out,
+        } => {
+            let content = aws::fetch_secret(profile, secret_id).await?;
+            let bytes = match key {
+                Some(path) => {
+                    let segments = json_path::parse_path(&path)?;
+                    let root = content.json_value()?;
+                    match json_path::get(&root, &segments)
+                        .ok_or_else(|| format!("Key '{path}' not found"))?
+                    {
+                        Value::String(s) => s.clone().into_bytes(),
+                        other => other.to_string().into_bytes(),
+                    }
+                }
+                None => secret_bytes(&content, out.is_some())?,
+            };
+            match out {
+                Some(path) => bundle::write_private_file(&path, &bytes)
+                    .map_err(|e| format!("Failed to write {}: {e}", path.display()))?,
+                None => {
+                    let mut stdout = std::io::stdout();
+                    stdout
+                        .write_all(&bytes)
+                        .and_then(|_| stdout.write_all(b"\n"))
+                        .map_err(|e| format!("Failed to write output: {e}"))?;
+                }
+            }
+        }
+        Command::Put {
+            secret_id,
+            value,
+            file,
+            binary,
+            description,
+            expected_version_id,
+            confirm,
+        } => {
+            let input = match (value, file) {
+                (Some(v), _) => v.into_bytes(),
+                (None, Some(path)) => std::fs::read(&path)
+                    .map_err(|e| format!("Failed to read {}: {e}", path.display()))?,
+                (None, None) => {
+                    let mut buf = Vec::new();
+                    std::io::stdin()
+                        .read_to_end(&mut buf)
+                        .map_err(|e| format!("Failed to read stdin: {e}"))?;
+                    buf
+                }
+            };
+            let secret_value = if binary {
+                base64::engine::general_purpose::STANDARD.encode(&input)
+            } else {
+                String::from_utf8(input)
+                    .map_err(|_| "Input is not valid UTF-8; use --binary".to_string())?
+            };
+            let client = aws_helper::secrets_client(profile.clone()).await;
+            let existing = aws::describe_if_exists(&client, &secret_id).await?;
+            let action = if existing.is_some() {
+                ProtectedAction::Update
+            } else {
+                ProtectedAction::Create
+            };
+            let token = confirmation_token(&profile, action, &secret_id, confirm)?;
+            guard::check_write(profile.as_deref(), action, &secret_id, token.as_deref())
+                .map_err(|e| e.to_string())?;
+            let message = aws::put_secret_with(
+                &client,
+                profile.as_deref(),
+                SecretWrite {
+                    secret_id,
+                    secret_value,
+                    description,
+                    is_binary: binary,
+                    kms_key_id: None,
+                    tags: Vec::new(),
+                },
+                existing.as_ref(),
+                expected_version_id,
+            )
+            .await
+            .map(|r| r.message)
+            .map_err(|e| e.to_string())?;
+            eprintln!("{message}");
+        }
+        Command::Delete { secret_id, confirm } => {
+            let token = confirmation_token(&profile, ProtectedAction::Delete, &secret_id, confirm)?;
+            let message = aws::delete_secret(profile, secret_id, token)
+                .await
+                .map_err(|e| e.to_string())?;
+            eprintln!("{message}");
+        }
+        Command::Restore { secret_id, confirm } => {
+            let token =
+                confirmation_token(&profile, ProtectedAction::Restore, &secret_id, confirm)?;
+            let message = aws::restore_secret(profile, secret_id, token)
+                .await
+                .map_err(|e| e.to_string())?;
+            eprintln!("{message}");
+        }
+        Command::Diff {
+            secret_id,
+            file,
+            other,
+            other_profile,
+            show_values,
+        } => {
+            let left = aws::fetch_secret(profile.clone(), secret_id).await?;
+            let left = String::from_utf8_lossy(&secret_bytes(&left, true)?).into_owned();
+            let right = match (file, other) {
+                (Some(path), _) => std::fs::read_to_string(&path)
+                    .map_err(|e| format!("Failed to read {}: {e}", path.display()))?,
+                (None, Some(other)) => {
+                    let content = aws::fetch_secret(other_profile.or(profile), other).await?;
+                    String::from_utf8_lossy(&secret_bytes(&content, true)?).into_owned()
+                }
+                (None, None) => return Err("Pass --file or --other".to_string()),
+            };
+            return Ok(print_diff(&left, &right, show_values));
+        }
+        Command::Export {
+            secret_ids,
+            prefix,
+            out,
+        } => {
+            let ids = bundle::select_secret_ids(profile.clone(), prefix, Some(secret_ids)).await?;
+            if ids.is_empty() {
+                return Err("No secrets match the selection".to_string());
+            }
+            let summary = bundle::export_bundle(profile, ids, &out, |id, done, total| {
+                eprintln!("[{done}/{total}] {id}");
+            })
+            .await?;
+            for f in &summary.failed {
+                eprintln!("failed: {}: {}", f.secret_id, f.error);
+            }
+            eprintln!("Exported {} secrets to {}", summary.exported, summary.path);
+            if !summary.failed.is_empty() {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Import {
+            path,
+            dry_run,
+            names,
+            confirm,
+        } => {
+            if dry_run {
+                let plan = bundle::plan_bundle(profile, &path).await?;
+                for item in plan
+                    .items
+                    .iter()
+                    .filter(|i| names.is_empty() || names.contains(&i.name))
+                {
+                    println!(
+                        "{:<9} {}{}",
+                        action_name(item.action),
+                        item.name,
+                        item.reason
+                            .as_deref()
+                            .map(|r| format!(" ({r})"))
+                            .unwrap_or_default()
+                    );
+                }
+                return Ok(ExitCode::SUCCESS);
+            }
+            let names = (!names.is_empty()).then_some(names);
+            let label = guard::profile_label(profile.as_deref()).to_string();
+            let token = confirmation_token(&profile, ProtectedAction::Import, &label, confirm)?;
+            guard::check_write(
+                profile.as_deref(),
+                ProtectedAction::Import,
+                &label,
+                token.as_deref(),
+            )
+            .map_err(|e| e.to_string())?;
+            let results = bundle::import_bundle(profile, &path, names, None, |r, done, total| {
+                eprintln!(
+                    "[{done}/{total}] {} {}: {}",
+                    action_name(r.action),
+                    r.name,
+                    r.message
+                );
+            })
+            .await?;
+            if results.iter().any(|r| !r.success) {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Lint { secret_ids, prefix } => {
+            let ids = bundle::select_secret_ids(profile.clone(), prefix, Some(secret_ids)).await?;
+            let report = lint::lint_secrets_with(profile, ids, |_, _, _| {}).await;
+            for f in &report.findings {
+                let level = match f.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                };
+                let path = if f.path.is_empty() {
+                    String::new()
+                } else {
+                    format!("#{}", f.path)
+                };
+                println!("{level:<7} {}{path}: {}", f.secret_id, f.message);
+            }
+            for f in &report.failed {
+                eprintln!("failed: {}: {}", f.secret_id, f.error);
+            }
+            eprintln!(
+                "Linted {} secrets, {} findings",
+                report.scanned,
+                report.findings.len()
+            );
+            if report
+                .findings
+                .iter()
+                .any(|f| f.severity == Severity::Error)
+                || !report.failed.is_empty()
+            {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Certs { prefix, days } => {
+            let ids = bundle::select_secret_ids(profile.clone(), prefix, None).await?;
+            let report =
+                certificates::scan_certificate_expiry_with(profile, ids, |_, _, _, _| {}).await;
+            for c in &report.certificates {
+                let path = if c.path.is_empty() {
+                    String::new()
+                } else {
+                    format!("#{}", c.path)
+                };
+                println!(
+                    "{:>6}d  {}  {}{path}  {}",
+                    c.certificate.days_remaining,
+                    c.certificate.not_after.as_deref().unwrap_or("-"),
+                    c.secret_id,
+                    c.certificate.subject
+                );
+            }
+            for s in &report.skipped {
+                eprintln!("skipped: {}: {}", s.secret_id, s.error);
+            }
+            for f in &report.failed {
+                eprintln!("failed: {}: {}", f.secret_id, f.error);
+            }
+            eprintln!(
+                "Found {} certificates in {} secrets",
+                report.certificates.len(),
+                report.scanned
+            );
+            if report
+                .certificates
+                .iter()
+                .any(|c| c.certificate.days_remaining < days)
+            {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Validate { prefix } => {
+            let report = schema::validate_under_prefix(profile, &prefix, |_, _, _| {}).await?;
+            for f in &report.failures {
+                if let Some(e) = &f.error {
+                    println!("{}: {e}", f.secret_id);
+                }
+                for v in &f.violations {
+                    let path = v.path.as_deref().filter(|p| !p.is_empty()).unwrap_or("/");
+                    println!("{} {path}: {}", f.secret_id, v.message);
+                }
+            }
+            eprintln!(
+                "{} of {} secrets match their schemas",
+                report.valid, report.checked
+            );
+            if !report.failures.is_empty() {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Token for a protected profile when the user passed `--confirm <secret name>`.
+fn confirmation_token(
+    profile: &Option<String>,
+    action: ProtectedAction,
+    secret_id: &str,
+    confirm: Option<String>,
+) -> Result<Option<String>, String> {
+    confirm
+        .map(|typed| {
+            guard::prepare_protected_write(profile.clone(), action, secret_id.to_string(), typed)
+                .map_err(|e| e.to_string())
+        })
+        .transpose()
+}
+
+/// Secret names from the app cache, refreshing it from AWS when asked or when empty.
+async fn list_names(profile: Option<String>, refresh: bool) -> Result<Vec<String>, String> {
+    let cache_key = profile.clone().unwrap_or_else(|| "default".to_string());
+    if !refresh {
+        if let Some(names) = config::load_cached_secret_names(&cache_key) {
+            return Ok(names);
+        }
+    }
+    let names = aws::list_secrets(profile).await?;
+    config::save_cached_secret_names(&cache_key, names.clone());
+    Ok(names)
+}
+
+/// Raw bytes of a secret; binary values are decoded when `raw` is set, base64 otherwise.
+fn secret_bytes(content: &SecretContent, raw: bool) -> Result<Vec<u8>, String> {
+    match (&content.string, &content.binary_base64) {
+        (Some(s), _) => Ok(s.clone().into_bytes()),
+        (None, Some(b64)) if raw => base64::engine::general_purpose::STANDARD
+            .decode(b64)
+            .map_err(|e| format!("Failed to decode base64: {e}")),
+        (None, Some(b64)) => Ok(b64.clone().into_bytes()),
+        (None, None) => Err("Secret has neither string nor binary".to_string()),
+    }
+}
+
+fn print_diff(left: &str, right: &str, show_values: bool) -> ExitCode {
+    let (Ok(a), Ok(b)) = (
+        serde_json::from_str::<Value>(left),
+        serde_json::from_str::<Value>(right),
+    ) else {
+        if left.trim_end() == right.trim_end() {
+            return ExitCode::SUCCESS;
+        }
+        println!("~ <value>");
+        return ExitCode::FAILURE;
+    };
+    let changes = json_diff::diff(&a, &b);
+    for c in &changes {
+        let path = if c.path.is_empty() { "<root>" } else { &c.path };
+        let sign = match c.kind {
+            ChangeKind::Added => '+',
+            ChangeKind::Removed => '-',
+            ChangeKind::Changed => '~',
+        };
+        if show_values {
+            let show = |v: &Option<Value>| v.as_ref().map(|v| v.to_string()).unwrap_or_default();
+            println!("{sign} {path}: {} -> {}", show(&c.before), show(&c.after));
+        } else {
+            println!("{sign} {path}");
+        }
+    }
+    if changes.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn action_name(action: ImportAction) -> &'static str {
+    match action {
+        ImportAction::Create => "create",
+        ImportAction::Update => "update",
+        ImportAction::Unchanged => "unchanged",
+        ImportAction::Conflict => "conflict",
+    }
+}
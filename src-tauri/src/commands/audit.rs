@@ -0,0 +1,19 @@
+use crate::helper::audit::{self, AuditRecord};
+
+// ==== Audit log (local, for change review) ====
+/// Audit records, newest first. `secret_id` matches exactly, or every secret under it when it
+/// ends with `/`; `from`/`to` are inclusive RFC 3339 timestamps.
+#[tauri::command]
+pub fn query_audit_log(
+    secret_id: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<AuditRecord>, String> {
+    audit::query(
+        secret_id.as_deref().filter(|s| !s.is_empty()),
+        from.as_deref(),
+        to.as_deref(),
+        limit,
+    )
+}
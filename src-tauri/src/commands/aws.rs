@@ -1,5 +1,12 @@
-use crate::commands::config::{SecretContent, SecretMetadata};
-use crate::helper::aws_helper;
+use crate::commands::config::{ReplicaStatus, SecretContent, SecretMetadata, SecretTag};
+use crate::commands::error::CommandError;
+use crate::commands::guard::{self, ProtectedAction};
+use crate::commands::team_policy;
+use crate::helper::audit::{self, AuditAction, AuditOutcome};
+use crate::helper::password::{self, PasswordOptions};
+use crate::helper::{aws_helper, json_path};
+use aws_sdk_secretsmanager::operation::describe_secret::DescribeSecretOutput;
+use aws_sdk_secretsmanager::types::Tag;
 use aws_smithy_runtime_api::client::{orchestrator::HttpResponse, result::SdkError};
 use aws_smithy_types::error::metadata::ProvideErrorMetadata;
 use base64::Engine as _;
@@ -49,12 +56,7 @@ pub async fn load_profiles() -> Result<Vec<String>, String> {
 // ==== AWS Secrets APIs ====
 #[tauri::command]
 pub async fn list_secrets(profile: Option<String>) -> Result<Vec<String>, String> {
-    let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest());
-    if let Some(p) = profile {
-        loader = loader.profile_name(p);
-    }
-    let config = loader.load().await;
-    let client = aws_sdk_secretsmanager::Client::new(&config);
+    let client = aws_helper::secrets_client(profile).await;
 
     let mut out = Vec::new();
     let mut next: Option<String> = None;
@@ -81,12 +83,7 @@ pub async fn list_secrets(profile: Option<String>) -> Result<Vec<String>, String
 pub async fn list_secrets_with_metadata(
     profile: Option<String>,
 ) -> Result<Vec<SecretMetadata>, String> {
-    let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest());
-    if let Some(p) = profile {
-        loader = loader.profile_name(p);
-    }
-    let config = loader.load().await;
-    let client = aws_sdk_secretsmanager::Client::new(&config);
+    let client = aws_helper::secrets_client(profile).await;
 
     let mut out = Vec::new();
     let mut next: Option<String> = None;
@@ -105,6 +102,13 @@ pub async fn list_secrets_with_metadata(
                 out.push(SecretMetadata {
                     name: n.to_string(),
                     is_binary: false, // Default, will be updated when fetched
+                    rotation_enabled: s.rotation_enabled().unwrap_or(false),
+                    rotation_lambda_arn: s.rotation_lambda_arn().map(|a| a.to_string()),
+                    last_rotated_date: s.last_rotated_date().and_then(aws_helper::format_date),
+                    next_rotation_date: s.next_rotation_date().and_then(aws_helper::format_date),
+                    kms_key_id: s.kms_key_id().map(|k| k.to_string()),
+                    primary_region: s.primary_region().map(|r| r.to_string()),
+                    replication_status: Vec::new(),
                 });
             }
         }
@@ -116,17 +120,42 @@ pub async fn list_secrets_with_metadata(
     Ok(out)
 }
 
+/// Full metadata for one secret, including per-region replication status.
+#[tauri::command]
+pub async fn describe_secret(
+    profile: Option<String>,
+    secret_id: String,
+) -> Result<SecretMetadata, String> {
+    let client = aws_helper::secrets_client(profile).await;
+    let resp = client
+        .describe_secret()
+        .secret_id(&secret_id)
+        .send()
+        .await
+        .map_err(|e| aws_helper::format_sdk_error(&e, &secret_id))?;
+    Ok(SecretMetadata {
+        name: resp.name().unwrap_or(&secret_id).to_string(),
+        is_binary: false, // describe_secret does not tell string from binary
+        rotation_enabled: resp.rotation_enabled().unwrap_or(false),
+        rotation_lambda_arn: resp.rotation_lambda_arn().map(|a| a.to_string()),
+        last_rotated_date: resp.last_rotated_date().and_then(aws_helper::format_date),
+        next_rotation_date: resp.next_rotation_date().and_then(aws_helper::format_date),
+        kms_key_id: resp.kms_key_id().map(|k| k.to_string()),
+        primary_region: resp.primary_region().map(|r| r.to_string()),
+        replication_status: resp
+            .replication_status()
+            .iter()
+            .map(ReplicaStatus::from)
+            .collect(),
+    })
+}
+
 #[tauri::command]
 pub async fn fetch_secret(
     profile: Option<String>,
     secret_id: String,
 ) -> Result<SecretContent, String> {
-    let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest());
-    if let Some(p) = profile {
-        loader = loader.profile_name(p);
-    }
-    let config = loader.load().await;
-    let client = aws_sdk_secretsmanager::Client::new(&config);
+    let client = aws_helper::secrets_client(profile).await;
     let resp = client
         .get_secret_value()
         .secret_id(&secret_id)
@@ -137,12 +166,14 @@ pub async fn fetch_secret(
         return Ok(SecretContent {
             string: Some(s),
             binary_base64: None,
+            version_id: resp.version_id,
         });
     }
     if let Some(b) = resp.secret_binary {
         return Ok(SecretContent {
             string: None,
             binary_base64: Some(base64::engine::general_purpose::STANDARD.encode(b.as_ref())),
+            version_id: resp.version_id,
         });
     }
     Err("Secret has neither string nor binary".to_string())
@@ -170,12 +201,7 @@ pub async fn fetch_secret_async(
     let secret_id_clone = secret_id.clone();
 
     tauri::async_runtime::spawn(async move {
-        let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest());
-        if let Some(p) = profile_clone {
-            loader = loader.profile_name(p);
-        }
-        let config = loader.load().await;
-        let client = aws_sdk_secretsmanager::Client::new(&config);
+        let client = aws_helper::secrets_client(profile_clone).await;
 
         match client
             .get_secret_value()
@@ -188,6 +214,7 @@ pub async fn fetch_secret_async(
                     SecretContent {
                         string: Some(s),
                         binary_base64: None,
+                        version_id: resp.version_id,
                     }
                 } else if let Some(b) = resp.secret_binary {
                     SecretContent {
@@ -195,6 +222,7 @@ pub async fn fetch_secret_async(
                         binary_base64: Some(
                             base64::engine::general_purpose::STANDARD.encode(b.as_ref()),
                         ),
+                        version_id: resp.version_id,
                     }
                 } else {
                     let _ = app.emit(
@@ -231,96 +259,427 @@ pub async fn fetch_secret_async(
     Ok(true)
 }
 
+/// Value and options written by `create_secret` / `update_secret`,
+/// shared with bulk operations (import, copy, move) that hold their own client.
+pub struct SecretWrite {
+    pub secret_id: String,
+    /// Plain string, or base64 when `is_binary` is true
+    pub secret_value: String,
+    pub description: Option<String>,
+    pub is_binary: bool,
+    pub kms_key_id: Option<String>,
+    /// Only applied when the secret is created
+    pub tags: Vec<SecretTag>,
+}
+
+/// Outcome of a create/update; `version_id` is the new AWSCURRENT version from the response.
+#[derive(Serialize, Clone)]
+pub struct SecretWriteResult {
+    pub message: String,
+    pub version_id: Option<String>,
+}
+
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn create_secret(
     profile: Option<String>,
     secret_id: String,
     secret_value: String,
     description: Option<String>,
     is_binary: Option<bool>,
-) -> Result<String, String> {
-    let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest());
-    if let Some(p) = profile {
-        loader = loader.profile_name(p);
-    }
-    let config = loader.load().await;
-    let client = aws_sdk_secretsmanager::Client::new(&config);
-    let mut req = client.create_secret().name(secret_id.clone());
+    kms_key_id: Option<String>,
+    tags: Option<Vec<SecretTag>>,
+    confirmation_token: Option<String>,
+) -> Result<SecretWriteResult, CommandError> {
+    guard::check_write(
+        profile.as_deref(),
+        ProtectedAction::Create,
+        &secret_id,
+        confirmation_token.as_deref(),
+    )?;
+    let write = SecretWrite {
+        secret_id,
+        secret_value,
+        description,
+        is_binary: is_binary == Some(true),
+        kms_key_id,
+        tags: tags.unwrap_or_default(),
+    };
+    team_policy::check_write_policy(profile.as_deref(), &write, None).await?;
+    let client = aws_helper::secrets_client(profile.clone()).await;
+    Ok(create_secret_with(&client, profile.as_deref(), write).await?)
+}
+
+pub async fn create_secret_with(
+    client: &aws_sdk_secretsmanager::Client,
+    profile: Option<&str>,
+    w: SecretWrite,
+) -> Result<SecretWriteResult, String> {
+    // No KMS key means the account default `aws/secretsmanager` key
+    let mut req = client
+        .create_secret()
+        .name(w.secret_id.clone())
+        .set_kms_key_id(w.kms_key_id);
 
     // If is_binary is true, decode base64 and use secret_binary
-    if is_binary == Some(true) {
+    if w.is_binary {
         let binary_data = base64::engine::general_purpose::STANDARD
-            .decode(&secret_value)
+            .decode(&w.secret_value)
             .map_err(|e| format!("Failed to decode base64: {e}"))?;
         req = req.secret_binary(binary_data.into());
     } else {
-        req = req.secret_string(secret_value);
+        req = req.secret_string(w.secret_value);
     }
 
-    if let Some(desc) = description {
+    if let Some(desc) = w.description {
         req = req.description(desc);
     }
-    let resp = req
-        .send()
-        .await
-        .map_err(|e| format_create_error(&e, &secret_id))?;
-    Ok(format!(
-        "Created secret: {}",
-        resp.name().unwrap_or("unknown")
-    ))
+    for tag in &w.tags {
+        req = req.tags(Tag::builder().key(&tag.key).value(&tag.value).build());
+    }
+    let result = req.send().await;
+    audit::record(
+        client,
+        profile,
+        AuditAction::Create,
+        &w.secret_id,
+        AuditOutcome::from_result(&result, |r| (r.arn(), r.version_id())),
+    )
+    .await;
+    let resp = result.map_err(|e| format_create_error(&e, &w.secret_id))?;
+    Ok(SecretWriteResult {
+        message: format!("Created secret: {}", resp.name().unwrap_or("unknown")),
+        version_id: resp.version_id,
+    })
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn update_secret(
     profile: Option<String>,
     secret_id: String,
     secret_value: String,
     description: Option<String>,
     is_binary: Option<bool>,
-) -> Result<String, String> {
-    let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest());
-    if let Some(p) = profile {
-        loader = loader.profile_name(p);
+    expected_version_id: Option<String>,
+    kms_key_id: Option<String>,
+    confirmation_token: Option<String>,
+) -> Result<SecretWriteResult, CommandError> {
+    guard::check_write(
+        profile.as_deref(),
+        ProtectedAction::Update,
+        &secret_id,
+        confirmation_token.as_deref(),
+    )?;
+    let write = SecretWrite {
+        secret_id,
+        secret_value,
+        description,
+        is_binary: is_binary == Some(true),
+        kms_key_id,
+        tags: Vec::new(),
+    };
+    let client = aws_helper::secrets_client(profile.clone()).await;
+    team_policy::enforce_write_policy(&client, profile.as_deref(), &write, true).await?;
+    update_secret_with(&client, profile.as_deref(), write, expected_version_id).await
+}
+
+pub async fn update_secret_with(
+    client: &aws_sdk_secretsmanager::Client,
+    profile: Option<&str>,
+    w: SecretWrite,
+    expected_version_id: Option<String>,
+) -> Result<SecretWriteResult, CommandError> {
+    let secret_id = w.secret_id;
+
+    // Optimistic concurrency: refuse to overwrite if AWSCURRENT moved since the caller fetched it
+    if let Some(expected) = expected_version_id {
+        let current = client
+            .get_secret_value()
+            .secret_id(&secret_id)
+            .version_stage("AWSCURRENT")
+            .send()
+            .await
+            .map_err(|e| format_get_error(&e, &secret_id))?;
+        if current.version_id() != Some(expected.as_str()) {
+            return Err(CommandError::VersionConflict {
+                message: format!(
+                    "Secret '{secret_id}' was changed by someone else since it was opened. Review the latest value and try again."
+                ),
+                secret_id,
+                expected_version_id: expected,
+                current: Box::new(SecretContent {
+                    binary_base64: current
+                        .secret_binary()
+                        .map(|b| base64::engine::general_purpose::STANDARD.encode(b.as_ref())),
+                    string: current.secret_string,
+                    version_id: current.version_id,
+                }),
+            });
+        }
     }
-    let config = loader.load().await;
-    let client = aws_sdk_secretsmanager::Client::new(&config);
-    let mut req = client.update_secret().secret_id(secret_id.clone());
+
+    // Changing the KMS key only re-encrypts the version created by this update
+    let mut req = client
+        .update_secret()
+        .secret_id(secret_id.clone())
+        .set_kms_key_id(w.kms_key_id);
 
     // If is_binary is true, decode base64 and use secret_binary
-    if is_binary == Some(true) {
+    if w.is_binary {
         let binary_data = base64::engine::general_purpose::STANDARD
-            .decode(&secret_value)
+            .decode(&w.secret_value)
             .map_err(|e| format!("Failed to decode base64: {e}"))?;
         req = req.secret_binary(binary_data.into());
     } else {
-        req = req.secret_string(secret_value);
+        req = req.secret_string(w.secret_value);
     }
 
-    if let Some(desc) = description {
+    if let Some(desc) = w.description {
         req = req.description(desc);
     }
-    let resp = req.send().await.map_err(|e| format_update_error(&e))?;
-    Ok(format!(
-        "Updated secret: {}",
-        resp.name().unwrap_or("unknown")
-    ))
+    let result = req.send().await;
+    audit::record(
+        client,
+        profile,
+        AuditAction::Update,
+        &secret_id,
+        AuditOutcome::from_result(&result, |r| (r.arn(), r.version_id())),
+    )
+    .await;
+    let resp = result.map_err(|e| format_update_error(&e))?;
+    Ok(SecretWriteResult {
+        message: format!("Updated secret: {}", resp.name().unwrap_or("unknown")),
+        version_id: resp.version_id,
+    })
+}
+
+/// The secret's metadata, or `None` when it does not exist.
+pub async fn describe_if_exists(
+    client: &aws_sdk_secretsmanager::Client,
+    secret_id: &str,
+) -> Result<Option<DescribeSecretOutput>, String> {
+    match client.describe_secret().secret_id(secret_id).send().await {
+        Ok(meta) => Ok(Some(meta)),
+        Err(e) if aws_helper::is_not_found(&e) => Ok(None),
+        Err(e) => Err(aws_helper::format_sdk_error(&e, secret_id)),
+    }
+}
+
+/// Update the secret if `existing` (from `describe_if_exists`) is set, otherwise create it.
+pub async fn put_secret_with(
+    client: &aws_sdk_secretsmanager::Client,
+    profile: Option<&str>,
+    w: SecretWrite,
+    existing: Option<&DescribeSecretOutput>,
+    expected_version_id: Option<String>,
+) -> Result<SecretWriteResult, CommandError> {
+    team_policy::check_write_policy(profile, &w, existing).await?;
+    if existing.is_some() {
+        update_secret_with(client, profile, w, expected_version_id).await
+    } else {
+        Ok(create_secret_with(client, profile, w).await?)
+    }
+}
+
+// ==== Single JSON key access ====
+#[tauri::command]
+pub async fn get_secret_json_value(
+    profile: Option<String>,
+    secret_id: String,
+    path: String,
+) -> Result<serde_json::Value, String> {
+    let segments = json_path::parse_path(&path)?;
+    let content = fetch_secret(profile, secret_id.clone()).await?;
+    let text = content
+        .string
+        .ok_or_else(|| format!("Secret '{secret_id}' is binary, not JSON"))?;
+    let root: serde_json::Value = serde_json::from_str(&text)
+        .map_err(|e| format!("Secret '{secret_id}' is not valid JSON: {e}"))?;
+    json_path::get(&root, &segments)
+        .cloned()
+        .ok_or_else(|| format!("Key '{path}' not found in secret '{secret_id}'"))
 }
 
 #[tauri::command]
-pub async fn delete_secret(profile: Option<String>, secret_id: String) -> Result<String, String> {
-    let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest());
-    if let Some(p) = profile {
-        loader = loader.profile_name(p);
+pub async fn update_secret_json_value(
+    profile: Option<String>,
+    secret_id: String,
+    path: String,
+    value: serde_json::Value,
+    confirmation_token: Option<String>,
+) -> Result<String, CommandError> {
+    guard::check_write(
+        profile.as_deref(),
+        ProtectedAction::Update,
+        &secret_id,
+        confirmation_token.as_deref(),
+    )?;
+    let segments = json_path::parse_path(&path)?;
+    let client = aws_helper::secrets_client(profile.clone()).await;
+
+    // Always patch the latest AWSCURRENT value so other keys edited elsewhere are kept
+    let current = client
+        .get_secret_value()
+        .secret_id(&secret_id)
+        .version_stage("AWSCURRENT")
+        .send()
+        .await
+        .map_err(|e| format_get_error(&e, &secret_id))?;
+    let text = current
+        .secret_string()
+        .ok_or_else(|| format!("Secret '{secret_id}' is binary, not JSON"))?;
+    let mut root: serde_json::Value = serde_json::from_str(text)
+        .map_err(|e| format!("Secret '{secret_id}' is not valid JSON: {e}"))?;
+    json_path::set(&mut root, &segments, value)?;
+
+    // Keep the original layout: pretty stays pretty, compact stays compact
+    let patched = if text.contains('\n') {
+        serde_json::to_string_pretty(&root)
+    } else {
+        serde_json::to_string(&root)
     }
-    let config = loader.load().await;
-    let client = aws_sdk_secretsmanager::Client::new(&config);
-    // Không force delete, giữ recovery window mặc định (30 ngày)
+    .map_err(|e| format!("Failed to serialize secret: {e}"))?;
+    let write = SecretWrite {
+        secret_id: secret_id.clone(),
+        secret_value: patched,
+        description: None,
+        is_binary: false,
+        kms_key_id: None,
+        tags: Vec::new(),
+    };
+    team_policy::enforce_write_policy(&client, profile.as_deref(), &write, true).await?;
+
+    // Expect the version just patched, so a concurrent write surfaces as a conflict
+    update_secret_with(&client, profile.as_deref(), write, current.version_id).await?;
+    Ok(format!("Updated key '{path}' in secret: {secret_id}"))
+}
+
+// ==== Random value generation ====
+async fn get_random_password(
+    client: &aws_sdk_secretsmanager::Client,
+    opts: &PasswordOptions,
+) -> Result<String, String> {
     let resp = client
-        .delete_secret()
-        .secret_id(&secret_id)
+        .get_random_password()
+        .password_length(opts.length())
+        .set_exclude_characters(opts.exclude_characters.clone())
+        .set_exclude_numbers(opts.exclude_numbers)
+        .set_exclude_punctuation(opts.exclude_punctuation)
+        .set_exclude_uppercase(opts.exclude_uppercase)
+        .set_exclude_lowercase(opts.exclude_lowercase)
+        .set_include_space(opts.include_space)
+        .set_require_each_included_type(opts.require_each_included_type)
         .send()
         .await
-        .map_err(|e| format_delete_error(&e, &secret_id))?;
+        .map_err(|e| format_random_password_error(&e))?;
+    resp.random_password
+        .ok_or_else(|| "GetRandomPassword returned no value".to_string())
+}
+
+#[tauri::command]
+pub async fn generate_secret_value(
+    profile: Option<String>,
+    options: Option<PasswordOptions>,
+    local: Option<bool>,
+) -> Result<String, String> {
+    let opts = options.unwrap_or_default();
+    if local == Some(true) {
+        return password::generate_local(&opts);
+    }
+    let client = aws_helper::secrets_client(profile).await;
+    get_random_password(&client, &opts).await
+}
+
+#[derive(Serialize, Clone)]
+pub struct GeneratedSecretResult {
+    pub message: String,
+    /// Only filled when the caller asked to reveal the generated value
+    pub value: Option<String>,
+}
+
+#[tauri::command]
+pub async fn create_generated_secret(
+    profile: Option<String>,
+    secret_id: String,
+    options: Option<PasswordOptions>,
+    json_key: Option<String>,
+    description: Option<String>,
+    reveal: Option<bool>,
+    confirmation_token: Option<String>,
+) -> Result<GeneratedSecretResult, CommandError> {
+    guard::check_write(
+        profile.as_deref(),
+        ProtectedAction::Create,
+        &secret_id,
+        confirmation_token.as_deref(),
+    )?;
+    // Name, description and KMS rules do not depend on the value, so check them before
+    // generating it; a binary write keeps the empty value out of the schema check
+    let mut write = SecretWrite {
+        secret_id,
+        secret_value: String::new(),
+        description,
+        is_binary: true,
+        kms_key_id: None,
+        tags: Vec::new(),
+    };
+    team_policy::check_write_policy(profile.as_deref(), &write, None).await?;
+
+    let opts = options.unwrap_or_default();
+    let client = aws_helper::secrets_client(profile.clone()).await;
+    let generated = get_random_password(&client, &opts).await?;
+
+    // Either the whole value is the password, or it is stored under one JSON key
+    write.secret_value = match &json_key {
+        Some(key) => {
+            let mut root = serde_json::json!({});
+            json_path::set(
+                &mut root,
+                &json_path::parse_path(key)?,
+                serde_json::Value::String(generated.clone()),
+            )?;
+            serde_json::to_string_pretty(&root)
+                .map_err(|e| format!("Failed to serialize secret: {e}"))?
+        }
+        None => generated.clone(),
+    };
+    write.is_binary = false;
+    team_policy::check_write_policy(profile.as_deref(), &write, None).await?;
+
+    let message = create_secret_with(&client, profile.as_deref(), write)
+        .await?
+        .message;
+    Ok(GeneratedSecretResult {
+        message,
+        value: (reveal == Some(true)).then_some(generated),
+    })
+}
+
+#[tauri::command]
+pub async fn delete_secret(
+    profile: Option<String>,
+    secret_id: String,
+    confirmation_token: Option<String>,
+) -> Result<String, CommandError> {
+    guard::check_write(
+        profile.as_deref(),
+        ProtectedAction::Delete,
+        &secret_id,
+        confirmation_token.as_deref(),
+    )?;
+    let client = aws_helper::secrets_client(profile.clone()).await;
+    // Không force delete, giữ recovery window mặc định (30 ngày)
+    let resp = client.delete_secret().secret_id(&secret_id).send().await;
+    audit::record(
+        &client,
+        profile.as_deref(),
+        AuditAction::Delete,
+        &secret_id,
+        AuditOutcome::from_result(&resp, |r| (r.arn(), None)),
+    )
+    .await;
+    let resp = resp.map_err(|e| format_delete_error(&e, &secret_id))?;
     Ok(format!(
         "Deleted secret: {}",
         resp.name().unwrap_or("unknown")
@@ -329,12 +688,7 @@ pub async fn delete_secret(profile: Option<String>, secret_id: String) -> Result
 
 #[tauri::command]
 pub async fn list_deleted_secrets(profile: Option<String>) -> Result<Vec<String>, String> {
-    let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest());
-    if let Some(p) = profile {
-        loader = loader.profile_name(p);
-    }
-    let config = loader.load().await;
-    let client = aws_sdk_secretsmanager::Client::new(&config);
+    let client = aws_helper::secrets_client(profile).await;
 
     let mut out = Vec::new();
     let mut next: Option<String> = None;
@@ -361,19 +715,28 @@ pub async fn list_deleted_secrets(profile: Option<String>) -> Result<Vec<String>
 }
 
 #[tauri::command]
-pub async fn restore_secret(profile: Option<String>, secret_id: String) -> Result<String, String> {
-    let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest());
-    if let Some(p) = profile {
-        loader = loader.profile_name(p);
-    }
-    let config = loader.load().await;
-    let client = aws_sdk_secretsmanager::Client::new(&config);
-    let resp = client
-        .restore_secret()
-        .secret_id(&secret_id)
-        .send()
-        .await
-        .map_err(|e| format_restore_error(&e, &secret_id))?;
+pub async fn restore_secret(
+    profile: Option<String>,
+    secret_id: String,
+    confirmation_token: Option<String>,
+) -> Result<String, CommandError> {
+    guard::check_write(
+        profile.as_deref(),
+        ProtectedAction::Restore,
+        &secret_id,
+        confirmation_token.as_deref(),
+    )?;
+    let client = aws_helper::secrets_client(profile.clone()).await;
+    let resp = client.restore_secret().secret_id(&secret_id).send().await;
+    audit::record(
+        &client,
+        profile.as_deref(),
+        AuditAction::Restore,
+        &secret_id,
+        AuditOutcome::from_result(&resp, |r| (r.arn(), None)),
+    )
+    .await;
+    let resp = resp.map_err(|e| format_restore_error(&e, &secret_id))?;
     Ok(format!(
         "Restored secret: {}",
         resp.name().unwrap_or("unknown")
@@ -382,8 +745,7 @@ pub async fn restore_secret(profile: Option<String>, secret_id: String) -> Resul
 
 #[tauri::command]
 pub async fn check_sso(profile: String) -> Result<bool, String> {
-    let loader = aws_config::defaults(aws_config::BehaviorVersion::latest()).profile_name(profile);
-    let config = loader.load().await;
+    let config = aws_helper::load_sdk_config(Some(profile)).await;
     let sts = aws_sdk_sts::Client::new(&config);
     match sts.get_caller_identity().send().await {
         Ok(_) => Ok(true),
@@ -417,9 +779,7 @@ pub async fn trigger_sso_login(app: tauri::AppHandle, profile: String) -> Result
     let app_handle = app.clone();
     let profile_clone = profile.clone();
     tauri::async_runtime::spawn(async move {
-        let loader = aws_config::defaults(aws_config::BehaviorVersion::latest())
-            .profile_name(profile_clone.clone());
-        let config = loader.load().await;
+        let config = aws_helper::load_sdk_config(Some(profile_clone.clone())).await;
         let sts = aws_sdk_sts::Client::new(&config);
         let mut success = false;
         for _ in 0..60 {
@@ -514,6 +874,34 @@ fn format_update_error(
     }
 }
 
+fn format_random_password_error(
+    e: &SdkError<
+        aws_sdk_secretsmanager::operation::get_random_password::GetRandomPasswordError,
+        HttpResponse,
+    >,
+) -> String {
+    match e {
+        SdkError::ServiceError(se) => {
+            let err = se.err();
+            let code = err.code().unwrap_or("");
+            match code {
+                "InvalidParameterException" => format!(
+                    "Invalid password options: {}",
+                    err.message()
+                        .unwrap_or("check length and excluded characters")
+                ),
+                _ => format!(
+                    "{code}: {}",
+                    err.message().unwrap_or("Unknown service error")
+                ),
+            }
+        }
+        SdkError::DispatchFailure(df) => format!("Network/dispatch error: {df:?}"),
+        SdkError::TimeoutError(te) => format!("Request timed out: {te:?}"),
+        other => format!("SDK error: {other:?}"),
+    }
+}
+
 fn format_list_error(
     e: &SdkError<aws_sdk_secretsmanager::operation::list_secrets::ListSecretsError, HttpResponse>,
 ) -> String {
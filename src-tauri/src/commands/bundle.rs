@@ -0,0 +1,664 @@
+use crate::commands::aws;
+use crate::commands::config::SecretTag;
+use crate::commands::error::CommandError;
+use crate::commands::guard::{self, ProtectedAction};
+use crate::commands::team_policy;
+use crate::helper::audit::{self, AuditAction, AuditOutcome};
+use crate::helper::aws_helper::{self, format_sdk_error};
+use aws_sdk_secretsmanager::operation::describe_secret::DescribeSecretOutput;
+use aws_sdk_secretsmanager::types::Tag;
+use aws_smithy_types::DateTime;
+use base64::Engine as _;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use tauri::Emitter;
+use tauri_plugin_dialog::DialogExt;
+
+// ==== Export bundle (backups / migrations between accounts) ====
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SecretBundle {
+    pub format_version: u32,
+    pub exported_at: Option<String>,
+    pub profile: Option<String>,
+    pub region: Option<String>,
+    pub secrets: Vec<BundleEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct BundleEntry {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<SecretTag>,
+    #[serde(default)]
+    pub is_binary: bool,
+    /// Plain string, or base64 when `is_binary` is true
+    pub value: String,
+}
+
+impl BundleEntry {
+    pub fn to_write(&self) -> aws::SecretWrite {
+        aws::SecretWrite {
+            secret_id: self.name.clone(),
+            secret_value: self.value.clone(),
+            description: self.description.clone(),
+            is_binary: self.is_binary,
+            kms_key_id: None,
+            tags: self.tags.clone(),
+        }
+    }
+}
+
+#[derive(Serialize, Clone)]
+pub struct BundleItemError {
+    pub secret_id: String,
+    pub error: String,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ExportSummary {
+    pub path: String,
+    pub exported: usize,
+    pub failed: Vec<BundleItemError>,
+}
+
+#[derive(Serialize, Clone)]
+struct ExportProgress {
+    secret_id: String,
+    done: usize,
+    total: usize,
+}
+
+/// Read value, description and tags of one secret (AWSCURRENT).
+pub async fn read_bundle_entry(
+    client: &aws_sdk_secretsmanager::Client,
+    secret_id: &str,
+) -> Result<BundleEntry, String> {
+    Ok(read_bundle_entry_and_meta(client, secret_id).await?.0)
+}
+
+/// Like `read_bundle_entry`, also returning the DescribeSecret output it was built from.
+pub async fn read_bundle_entry_and_meta(
+    client: &aws_sdk_secretsmanager::Client,
+    secret_id: &str,
+) -> Result<(BundleEntry, DescribeSecretOutput), String> {
+    let meta = client
+        .describe_secret()
+        .secret_id(secret_id)
+        .send()
+        .await
+        .map_err(|e| format_sdk_error(&e, secret_id))?;
+    let entry = bundle_entry_from_meta(client, secret_id, &meta).await?;
+    Ok((entry, meta))
+}
+
+/// Build the entry for an already described secret; only fetches the value.
+async fn bundle_entry_from_meta(
+    client: &aws_sdk_secretsmanager::Client,
+    secret_id: &str,
+    meta: &DescribeSecretOutput,
+) -> Result<BundleEntry, String> {
+    let value = client
+        .get_secret_value()
+        .secret_id(secret_id)
+        .send()
+        .await
+        .map_err(|e| format_sdk_error(&e, secret_id))?;
+    let (is_binary, value) = if let Some(s) = value.secret_string {
+        (false, s)
+    } else if let Some(b) = value.secret_binary {
+        (
+            true,
+            base64::engine::general_purpose::STANDARD.encode(b.as_ref()),
+        )
+    } else {
+        return Err(format!(
+            "Secret '{secret_id}' has neither string nor binary"
+        ));
+    };
+    Ok(BundleEntry {
+        name: meta.name().unwrap_or(secret_id).to_string(),
+        description: meta.description().map(|s| s.to_string()),
+        tags: meta
+            .tags()
+            .iter()
+            .filter_map(|t| {
+                Some(SecretTag {
+                    key: t.key()?.to_string(),
+                    value: t.value().unwrap_or_default().to_string(),
+                })
+            })
+            .collect(),
+        is_binary,
+        value,
+    })
+}
+
+pub fn read_bundle(path: &Path) -> Result<SecretBundle, String> {
+    let data = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read bundle {}: {e}", path.display()))?;
+    let bundle: SecretBundle =
+        serde_json::from_str(&data).map_err(|e| format!("Invalid bundle file: {e}"))?;
+    if bundle.format_version > BUNDLE_FORMAT_VERSION {
+        return Err(format!(
+            "Bundle format version {} is newer than supported ({BUNDLE_FORMAT_VERSION})",
+            bundle.format_version
+        ));
+    }
+    Ok(bundle)
+}
+
+/// Bundles hold plaintext values, so keep them readable by the owner only.
+pub fn write_bundle(path: &Path, bundle: &SecretBundle) -> Result<(), String> {
+    let data = serde_json::to_vec_pretty(bundle)
+        .map_err(|e| format!("Failed to serialize bundle: {e}"))?;
+    write_private_file(path, &data)
+        .map_err(|e| format!("Failed to write bundle {}: {e}", path.display()))
+}
+
+/// Write `data` so only the owner can read it (0600 on unix), also when overwriting.
+pub fn write_private_file(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    // `mode` only applies to new files; tighten a file that is being overwritten too
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(data)
+}
+
+/// Secrets selected by explicit ids, or every secret whose name starts with `prefix`.
+pub async fn select_secret_ids(
+    profile: Option<String>,
+    prefix: Option<String>,
+    secret_ids: Option<Vec<String>>,
+) -> Result<Vec<String>, String> {
+    if let Some(ids) = secret_ids.filter(|ids| !ids.is_empty()) {
+        return Ok(ids);
+    }
+    let prefix = prefix.unwrap_or_default();
+    let mut names: Vec<String> = aws::list_secrets(profile)
+        .await?
+        .into_iter()
+        .filter(|n| n.starts_with(&prefix))
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+/// Export secrets into one JSON bundle. When `path` is not given, a save dialog is shown.
+/// Returns `Ok(None)` if the dialog was cancelled.
+#[tauri::command]
+pub async fn export_secrets(
+    app: tauri::AppHandle,
+    profile: Option<String>,
+    prefix: Option<String>,
+    secret_ids: Option<Vec<String>>,
+    path: Option<String>,
+) -> Result<Option<ExportSummary>, String> {
+    let ids = select_secret_ids(profile.clone(), prefix.clone(), secret_ids).await?;
+    if ids.is_empty() {
+        return Err("No secrets match the selection".to_string());
+    }
+
+    let path = match path {
+        Some(p) => PathBuf::from(p),
+        None => {
+            let default_name = format!(
+                "secrets_{}.json",
+                prefix
+                    .as_deref()
+                    .unwrap_or("export")
+                    .trim_end_matches('/')
+                    .replace('/', "_")
+            );
+            let dialog = app
+                .dialog()
+                .file()
+                .add_filter("JSON", &["json"])
+                .set_file_name(default_name);
+            let picked = tauri::async_runtime::spawn_blocking(move || dialog.blocking_save_file())
+                .await
+                .map_err(|e| format!("Save dialog failed: {e}"))?;
+            match picked {
+                Some(p) => p
+                    .into_path()
+                    .map_err(|e| format!("Invalid save path: {e}"))?,
+                None => return Ok(None),
+            }
+        }
+    };
+
+    let summary = export_bundle(profile, ids, &path, |secret_id, done, total| {
+        let _ = app.emit(
+            "export_progress",
+            ExportProgress {
+                secret_id: secret_id.to_string(),
+                done,
+                total,
+            },
+        );
+    })
+    .await?;
+    Ok(Some(summary))
+}
+
+/// Write the given secrets of `profile` to a bundle file, reporting `(secret_id, done, total)` as it goes.
+pub async fn export_bundle(
+    profile: Option<String>,
+    ids: Vec<String>,
+    path: &Path,
+    mut on_progress: impl FnMut(&str, usize, usize),
+) -> Result<ExportSummary, String> {
+    let config = aws_helper::load_sdk_config(profile.clone()).await;
+    let client = aws_sdk_secretsmanager::Client::new(&config);
+    let total = ids.len();
+    let mut secrets = Vec::with_capacity(total);
+    let mut failed = Vec::new();
+    for (i, id) in ids.into_iter().enumerate() {
+        match read_bundle_entry(&client, &id).await {
+            Ok(entry) => secrets.push(entry),
+            Err(error) => failed.push(BundleItemError {
+                secret_id: id.clone(),
+                error,
+            }),
+        }
+        on_progress(&id, i + 1, total);
+    }
+
+    let bundle = SecretBundle {
+        format_version: BUNDLE_FORMAT_VERSION,
+        exported_at: aws_helper::format_date(&DateTime::from(std::time::SystemTime::now())),
+        profile,
+        region: config.region().map(|r| r.to_string()),
+        secrets,
+    };
+    write_bundle(path, &bundle)?;
+    Ok(ExportSummary {
+        path: path.display().to_string(),
+        exported: bundle.secrets.len(),
+        failed,
+    })
+}
+
+/// Add (or overwrite) tags on a secret. Tags not listed are left untouched.
+pub async fn apply_tags(
+    client: &aws_sdk_secretsmanager::Client,
+    profile: Option<&str>,
+    secret_id: &str,
+    tags: &[SecretTag],
+) -> Result<(), String> {
+    if tags.is_empty() {
+        return Ok(());
+    }
+    let tags = tags
+        .iter()
+        .map(|t| Tag::builder().key(&t.key).value(&t.value).build())
+        .collect();
+    let resp = client
+        .tag_resource()
+        .secret_id(secret_id)
+        .set_tags(Some(tags))
+        .send()
+        .await;
+    audit::record(
+        client,
+        profile,
+        AuditAction::Tag,
+        secret_id,
+        AuditOutcome::from_result(&resp, |_| (None, None)),
+    )
+    .await;
+    resp.map_err(|e| format_sdk_error(&e, secret_id))?;
+    Ok(())
+}
+
+// ==== Import bundle (dry-run plan, then apply) ====
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportAction {
+    Create,
+    Update,
+    Unchanged,
+    Conflict,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ImportPlanItem {
+    pub name: String,
+    pub action: ImportAction,
+    pub reason: Option<String>,
+    /// AWSCURRENT version the plan was made against; an update expects it to be unchanged
+    pub version_id: Option<String>,
+    /// Whether the update changes the value, or only tags/description
+    #[serde(skip)]
+    pub value_changed: bool,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ImportPlan {
+    pub path: String,
+    pub source_profile: Option<String>,
+    pub items: Vec<ImportPlanItem>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ImportItemResult {
+    pub name: String,
+    pub action: ImportAction,
+    pub success: bool,
+    pub message: String,
+}
+
+#[derive(Serialize, Clone)]
+struct ImportProgress {
+    name: String,
+    action: ImportAction,
+    success: bool,
+    done: usize,
+    total: usize,
+}
+
+/// Compare one bundle entry against what currently exists in the target account.
+pub async fn plan_entry(
+    client: &aws_sdk_secretsmanager::Client,
+    entry: &BundleEntry,
+) -> Result<ImportPlanItem, String> {
+    let item = |action, reason: Option<&str>| ImportPlanItem {
+        name: entry.name.clone(),
+        action,
+        reason: reason.map(|r| r.to_string()),
+        version_id: None,
+        value_changed: false,
+    };
+    let meta = match client.describe_secret().secret_id(&entry.name).send().await {
+        Ok(meta) if meta.deleted_date().is_some() => {
+            return Ok(item(
+                ImportAction::Conflict,
+                Some("Secret is scheduled for deletion; restore it first"),
+            ))
+        }
+        Ok(meta) => meta,
+        Err(e) if aws_helper::is_not_found(&e) => return Ok(item(ImportAction::Create, None)),
+        Err(e) => return Err(format_sdk_error(&e, &entry.name)),
+    };
+    let version_id = meta
+        .version_ids_to_stages()
+        .and_then(|m| {
+            m.iter()
+                .find(|(_, stages)| stages.iter().any(|s| s == "AWSCURRENT"))
+        })
+        .map(|(id, _)| id.clone());
+
+    let current = bundle_entry_from_meta(client, &entry.name, &meta).await?;
+    if current.is_binary != entry.is_binary {
+        return Ok(item(
+            ImportAction::Conflict,
+            Some(if entry.is_binary {
+                "Bundle has a binary value but the existing secret is a string"
+            } else {
+                "Bundle has a string value but the existing secret is binary"
+            }),
+        ));
+    }
+    let tags_missing = entry.tags.iter().any(|t| !current.tags.contains(t));
+    if current.value == entry.value
+        && (entry.description.is_none() || current.description == entry.description)
+        && !tags_missing
+    {
+        return Ok(item(ImportAction::Unchanged, None));
+    }
+    let value_changed = current.value != entry.value;
+    let reason = if value_changed {
+        "Value differs"
+    } else if tags_missing {
+        "Tags differ"
+    } else {
+        "Description differs"
+    };
+    Ok(ImportPlanItem {
+        version_id,
+        value_changed,
+        ..item(ImportAction::Update, Some(reason))
+    })
+}
+
+async fn pick_bundle_path(
+    app: &tauri::AppHandle,
+    path: Option<String>,
+) -> Result<Option<PathBuf>, String> {
+    if let Some(p) = path {
+        return Ok(Some(PathBuf::from(p)));
+    }
+    let dialog = app.dialog().file().add_filter("JSON", &["json"]);
+    let picked = tauri::async_runtime::spawn_blocking(move || dialog.blocking_pick_file())
+        .await
+        .map_err(|e| format!("Open dialog failed: {e}"))?;
+    picked
+        .map(|p| p.into_path().map_err(|e| format!("Invalid file path: {e}")))
+        .transpose()
+}
+
+/// Dry run: read the bundle and report what importing it into `profile` would do.
+/// When `path` is not given, an open dialog is shown; `Ok(None)` means it was cancelled.
+#[tauri::command]
+pub async fn plan_import(
+    app: tauri::AppHandle,
+    profile: Option<String>,
+    path: Option<String>,
+) -> Result<Option<ImportPlan>, String> {
+    let Some(path) = pick_bundle_path(&app, path).await? else {
+        return Ok(None);
+    };
+    plan_bundle(profile, &path).await.map(Some)
+}
+
+/// Dry-run plan for importing the bundle at `path` into `profile`. An entry that cannot be
+/// planned is reported as a conflict with the error, so the rest are still planned.
+pub async fn plan_bundle(profile: Option<String>, path: &Path) -> Result<ImportPlan, String> {
+    let bundle = read_bundle(path)?;
+    let client = aws_helper::secrets_client(profile).await;
+    let mut items = Vec::with_capacity(bundle.secrets.len());
+    for entry in &bundle.secrets {
+        let item = plan_entry(&client, entry)
+            .await
+            .unwrap_or_else(|error| ImportPlanItem {
+                name: entry.name.clone(),
+                action: ImportAction::Conflict,
+                reason: Some(error),
+                version_id: None,
+                value_changed: false,
+            });
+        items.push(item);
+    }
+    Ok(ImportPlan {
+        path: path.display().to_string(),
+        source_profile: bundle.profile,
+        items,
+    })
+}
+
+/// Apply a bundle. Each entry is planned again right before it is written, and an update
+/// fails instead of overwriting when the secret changed after that plan; conflicts are always
+/// skipped. `names` limits the import to the selected entries. `reviewed_versions` maps each
+/// name to the `version_id` of the reviewed dry-run plan; an entry whose secret moved since
+/// then is reported as a conflict.
+#[tauri::command]
+pub async fn apply_import(
+    app: tauri::AppHandle,
+    profile: Option<String>,
+    path: String,
+    names: Option<Vec<String>>,
+    reviewed_versions: Option<HashMap<String, Option<String>>>,
+    confirmation_token: Option<String>,
+) -> Result<Vec<ImportItemResult>, CommandError> {
+    guard::check_write(
+        profile.as_deref(),
+        ProtectedAction::Import,
+        guard::profile_label(profile.as_deref()),
+        confirmation_token.as_deref(),
+    )?;
+    Ok(import_bundle(
+        profile,
+        Path::new(&path),
+        names,
+        reviewed_versions,
+        |result, done, total| {
+            let _ = app.emit(
+                "import_progress",
+                ImportProgress {
+                    name: result.name.clone(),
+                    action: result.action,
+                    success: result.success,
+                    done,
+                    total,
+                },
+            );
+        },
+    )
+    .await?)
+}
+
+/// Re-plan and apply the bundle at `path`, reporting each result with `(done, total)`.
+pub async fn import_bundle(
+    profile: Option<String>,
+    path: &Path,
+    names: Option<Vec<String>>,
+    reviewed_versions: Option<HashMap<String, Option<String>>>,
+    mut on_progress: impl FnMut(&ImportItemResult, usize, usize),
+) -> Result<Vec<ImportItemResult>, String> {
+    let bundle = read_bundle(path)?;
+    let client = aws_helper::secrets_client(profile.clone()).await;
+    let entries: Vec<BundleEntry> = bundle
+        .secrets
+        .into_iter()
+        .filter(|e| names.as_ref().is_none_or(|n| n.contains(&e.name)))
+        .collect();
+
+    let total = entries.len();
+    let mut results = Vec::with_capacity(total);
+    for (i, entry) in entries.into_iter().enumerate() {
+        let result = match plan_entry(&client, &entry).await {
+            Ok(plan) => {
+                let reviewed = reviewed_versions.as_ref().and_then(|v| v.get(&entry.name));
+                let plan = match reviewed {
+                    Some(version) if plan.action != ImportAction::Unchanged && *version != plan.version_id => {
+                        ImportPlanItem {
+                            action: ImportAction::Conflict,
+                            reason: Some(
+                                "Secret changed after the import was reviewed; run the dry-run again"
+                                    .to_string(),
+                            ),
+                            ..plan
+                        }
+                    }
+                    _ => plan,
+                };
+                apply_entry(&client, profile.as_deref(), &entry, plan).await
+            }
+            Err(error) => ImportItemResult {
+                name: entry.name,
+                action: ImportAction::Conflict,
+                success: false,
+                message: error,
+            },
+        };
+        on_progress(&result, i + 1, total);
+        results.push(result);
+    }
+    Ok(results)
+}
+
+/// Carry out one planned item with the target account's client. Conflicts are never applied.
+pub async fn apply_entry(
+    client: &aws_sdk_secretsmanager::Client,
+    profile: Option<&str>,
+    entry: &BundleEntry,
+    plan: ImportPlanItem,
+) -> ImportItemResult {
+    let outcome = match plan.action {
+        ImportAction::Unchanged => Ok("Unchanged".to_string()),
+        ImportAction::Conflict => Err(plan.reason.unwrap_or_else(|| "Conflict".to_string())),
+        ImportAction::Create => {
+            let write = entry.to_write();
+            match team_policy::check_write_policy(profile, &write, None).await {
+                Ok(()) => aws::create_secret_with(client, profile, write)
+                    .await
+                    .map(|r| r.message),
+                Err(e) => Err(e.to_string()),
+            }
+        }
+        ImportAction::Update => {
+            let write = entry.to_write();
+            match team_policy::enforce_write_policy(client, profile, &write, true).await {
+                Ok(()) if plan.value_changed => {
+                    aws::update_secret_with(client, profile, write, plan.version_id.clone())
+                        .await
+                        .map(|r| r.message)
+                        .map_err(|e| e.to_string())
+                }
+                // Same value: touch only the metadata so no new version is created
+                Ok(()) => update_description(client, profile, &entry.name, &entry.description)
+                    .await
+                    .map(|_| format!("Updated metadata: {}", entry.name)),
+                Err(e) => Err(e.to_string()),
+            }
+        }
+    };
+    let outcome = match outcome {
+        // A create already sets the tags; an update only adds the missing ones
+        Ok(message) if plan.action == ImportAction::Update => {
+            apply_tags(client, profile, &entry.name, &entry.tags)
+                .await
+                .map(|_| message)
+        }
+        other => other,
+    };
+    ImportItemResult {
+        name: entry.name.clone(),
+        action: plan.action,
+        success: outcome.is_ok(),
+        message: outcome.unwrap_or_else(|e| e),
+    }
+}
+
+/// Set the description without writing a new version; a missing description is left as is.
+async fn update_description(
+    client: &aws_sdk_secretsmanager::Client,
+    profile: Option<&str>,
+    secret_id: &str,
+    description: &Option<String>,
+) -> Result<(), String> {
+    let Some(description) = description else {
+        return Ok(());
+    };
+    let resp = client
+        .update_secret()
+        .secret_id(secret_id)
+        .description(description)
+        .send()
+        .await;
+    audit::record(
+        client,
+        profile,
+        AuditAction::Update,
+        secret_id,
+        AuditOutcome::from_result(&resp, |r| (r.arn(), None)),
+    )
+    .await;
+    resp.map_err(|e| format_sdk_error(&e, secret_id))?;
+    Ok(())
+}
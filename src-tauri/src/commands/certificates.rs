@@ -0,0 +1,220 @@
+use crate::commands::aws;
+use crate::commands::bundle::{self, BundleItemError};
+use crate::commands::config::SecretContent;
+use crate::helper::aws_helper;
+use crate::helper::cert::{self, CertificateDetails, Inspection};
+use crate::helper::{lint, pem};
+use base64::Engine as _;
+use serde::Serialize;
+use tauri::Emitter;
+
+// ==== Certificate and key inspection ====
+/// Raw bytes of a secret value. A string holding only base64 (e.g. a DER or PKCS#12 file
+/// pasted as text) is decoded first.
+pub fn content_bytes(content: &SecretContent) -> Result<Vec<u8>, String> {
+    let engine = base64::engine::general_purpose::STANDARD;
+    if let Some(b64) = &content.binary_base64 {
+        return engine
+            .decode(b64)
+            .map_err(|e| format!("Invalid base64 data: {e}"));
+    }
+    Ok(text_bytes(content.string.as_deref().unwrap_or_default()))
+}
+
+fn text_bytes(text: &str) -> Vec<u8> {
+    let compact: String = text.split_whitespace().collect();
+    match base64::engine::general_purpose::STANDARD.decode(&compact) {
+        Ok(bytes) if !compact.is_empty() && !text.trim_start().starts_with('{') => bytes,
+        _ => text.as_bytes().to_vec(),
+    }
+}
+
+/// Certificates and keys found in the current value of a secret. Private key material is
+/// never returned, only its type, size and whether it is encrypted.
+#[tauri::command]
+pub async fn inspect_secret_certificates(
+    profile: Option<String>,
+    secret_id: String,
+    password: Option<String>,
+) -> Result<Inspection, String> {
+    let content = aws::fetch_secret(profile, secret_id).await?;
+    let bytes = content_bytes(&content)?;
+    Ok(cert::inspect(&bytes, password.as_deref(), cert::now_secs()))
+}
+
+/// Same as `inspect_secret_certificates` for data not stored yet, such as an imported file.
+#[tauri::command]
+pub fn inspect_certificate_data(
+    data_base64: String,
+    password: Option<String>,
+) -> Result<Inspection, String> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(data_base64.trim())
+        .map_err(|e| format!("Invalid base64 data: {e}"))?;
+    Ok(cert::inspect(&bytes, password.as_deref(), cert::now_secs()))
+}
+
+// ==== Certificate expiry scan ====
+/// Base64 shorter than this is unlikely to be a certificate and is not decoded.
+const MIN_EMBEDDED_BASE64_LEN: usize = 200;
+
+#[derive(Serialize, Clone)]
+pub struct CertificateExpiry {
+    pub secret_id: String,
+    /// JSON pointer of the key holding the certificate; empty for the whole value
+    pub path: String,
+    #[serde(flatten)]
+    pub certificate: CertificateDetails,
+}
+
+#[derive(Serialize, Clone)]
+pub struct CertificateScanReport {
+    pub scanned: usize,
+    /// Soonest expiry first
+    pub certificates: Vec<CertificateExpiry>,
+    /// Password-protected PKCS#12 bundles whose certificates were not read
+    pub skipped: Vec<BundleItemError>,
+    pub failed: Vec<BundleItemError>,
+}
+
+/// What a secret value holds, for the expiry scan.
+#[derive(Default)]
+struct ScannedValue {
+    certificates: Vec<(String, CertificateDetails)>,
+    /// Paths of bundles that need a password
+    password_required: Vec<String>,
+    /// Path and message for data that could not be read
+    errors: Vec<(String, String)>,
+}
+
+#[derive(Serialize, Clone)]
+struct CertificateScanProgress {
+    secret_id: String,
+    done: usize,
+    total: usize,
+    found: usize,
+}
+
+/// Certificates in a secret value: PEM or base64 text, JSON string values, or binary data.
+fn certificates_in(string: Option<&str>, binary: Option<&[u8]>, now_secs: i64) -> ScannedValue {
+    let mut out = ScannedValue::default();
+    let mut add = |path: &str, bytes: &[u8]| {
+        let inspection = cert::inspect(bytes, None, now_secs);
+        if inspection.password_required {
+            out.password_required.push(path.to_string());
+        } else {
+            out.errors
+                .extend(inspection.errors.into_iter().map(|e| (path.to_string(), e)));
+        }
+        for c in inspection.certificates {
+            out.certificates.push((path.to_string(), c));
+        }
+    };
+    if let Some(bytes) = binary {
+        add("", bytes);
+    } else if let Some(text) = string {
+        for leaf in lint::leaves(text) {
+            let embedded = leaf.value.len() >= MIN_EMBEDDED_BASE64_LEN;
+            if pem::contains_pem(&leaf.value) {
+                add(&leaf.path, leaf.value.as_bytes());
+            } else if leaf.key.is_none() || embedded {
+                add(&leaf.path, &text_bytes(&leaf.value));
+            }
+        }
+    }
+    out
+}
+
+/// Find every certificate in the given secrets and sort them by expiry.
+pub async fn scan_certificate_expiry_with(
+    profile: Option<String>,
+    ids: Vec<String>,
+    mut on_progress: impl FnMut(&str, usize, usize, usize),
+) -> CertificateScanReport {
+    let client = aws_helper::secrets_client(profile).await;
+    let now = cert::now_secs();
+    let total = ids.len();
+    let mut report = CertificateScanReport {
+        scanned: 0,
+        certificates: Vec::new(),
+        skipped: Vec::new(),
+        failed: Vec::new(),
+    };
+    for (i, secret_id) in ids.iter().enumerate() {
+        match client.get_secret_value().secret_id(secret_id).send().await {
+            Err(e) => report.failed.push(BundleItemError {
+                secret_id: secret_id.clone(),
+                error: aws_helper::format_sdk_error(&e, secret_id),
+            }),
+            Ok(resp) => {
+                report.scanned += 1;
+                let found = certificates_in(
+                    resp.secret_string(),
+                    resp.secret_binary().map(|b| b.as_ref()),
+                    now,
+                );
+                let at = |path: &str| {
+                    if path.is_empty() {
+                        String::new()
+                    } else {
+                        format!("{path}: ")
+                    }
+                };
+                report
+                    .skipped
+                    .extend(found.password_required.iter().map(|path| BundleItemError {
+                        secret_id: secret_id.clone(),
+                        error: format!("{}password required", at(path)),
+                    }));
+                report
+                    .failed
+                    .extend(found.errors.iter().map(|(path, e)| BundleItemError {
+                        secret_id: secret_id.clone(),
+                        error: format!("{}{e}", at(path)),
+                    }));
+                report
+                    .certificates
+                    .extend(found.certificates.into_iter().map(|(path, certificate)| {
+                        CertificateExpiry {
+                            secret_id: secret_id.clone(),
+                            path,
+                            certificate,
+                        }
+                    }));
+            }
+        }
+        on_progress(secret_id, i + 1, total, report.certificates.len());
+    }
+    report.certificates.sort_by(|a, b| {
+        (a.certificate.not_after_epoch, &a.secret_id, &a.path).cmp(&(
+            b.certificate.not_after_epoch,
+            &b.secret_id,
+            &b.path,
+        ))
+    });
+    report
+}
+
+/// Walk the secrets under `prefix` and list their certificates by expiry date.
+#[tauri::command]
+pub async fn scan_certificate_expiry(
+    app: tauri::AppHandle,
+    profile: Option<String>,
+    prefix: Option<String>,
+) -> Result<CertificateScanReport, String> {
+    let ids = bundle::select_secret_ids(profile.clone(), prefix, None).await?;
+    Ok(
+        scan_certificate_expiry_with(profile, ids, |secret_id, done, total, found| {
+            let _ = app.emit(
+                "certificate_scan_progress",
+                CertificateScanProgress {
+                    secret_id: secret_id.to_string(),
+                    done,
+                    total,
+                    found,
+                },
+            );
+        })
+        .await,
+    )
+}
@@ -1,3 +1,6 @@
+use crate::helper::dotenv;
+use crate::helper::team_policy::SchemaRule;
+use crate::helper::value_schema::SchemaSet;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
@@ -6,13 +9,74 @@ use std::path::PathBuf;
 pub struct SecretMetadata {
     pub name: String,
     pub is_binary: bool,
+    #[serde(default)]
+    pub rotation_enabled: bool,
+    #[serde(default)]
+    pub rotation_lambda_arn: Option<String>,
+    #[serde(default)]
+    pub last_rotated_date: Option<String>,
+    #[serde(default)]
+    pub next_rotation_date: Option<String>,
+    /// `None` means the default `aws/secretsmanager` key
+    #[serde(default)]
+    pub kms_key_id: Option<String>,
+    #[serde(default)]
+    pub primary_region: Option<String>,
+    /// Only filled by `describe_secret`; `list_secrets` does not return replica details
+    #[serde(default)]
+    pub replication_status: Vec<ReplicaStatus>,
 }
 
-// ==== Types shared to FE ====
 #[derive(Serialize, Deserialize, Clone)]
+pub struct ReplicaStatus {
+    pub region: String,
+    pub status: Option<String>,
+    pub status_message: Option<String>,
+    pub kms_key_id: Option<String>,
+    pub last_accessed_date: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct SecretTag {
+    pub key: String,
+    pub value: String,
+}
+
+// ==== Types shared to FE ====
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct SecretContent {
     pub string: Option<String>,
     pub binary_base64: Option<String>,
+    /// Version id of the fetched value, passed back to `update_secret` for conflict detection
+    #[serde(default)]
+    pub version_id: Option<String>,
+}
+
+impl SecretContent {
+    /// Parse the string value as JSON; binary secrets are rejected.
+    pub fn json_value(&self) -> Result<serde_json::Value, String> {
+        let text = self
+            .string
+            .as_deref()
+            .ok_or_else(|| "Binary secrets cannot be converted".to_string())?;
+        serde_json::from_str(text).map_err(|e| format!("Secret value is not valid JSON: {e}"))
+    }
+
+    pub fn to_dotenv(&self) -> Result<String, String> {
+        dotenv::to_dotenv(&self.json_value()?)
+    }
+
+    /// Build a pretty-printed JSON secret from `.env` content.
+    pub fn from_dotenv(content: &str) -> Result<SecretContent, String> {
+        let map = dotenv::parse_dotenv(content)?;
+        let text = serde_json::to_string_pretty(&serde_json::Value::Object(map))
+            .map_err(|e| format!("Failed to serialize secret: {e}"))?;
+        Ok(SecretContent {
+            string: Some(text),
+            binary_base64: None,
+            version_id: None,
+        })
+    }
 }
 
 // ==== Config (cache + default profile) ====
@@ -49,12 +113,22 @@ pub fn load_default_profile() -> Option<String> {
 
 #[tauri::command]
 pub fn save_default_profile(profile: &str) -> bool {
-    if let Some(path) = config_store_path() {
-        let _ = fs::create_dir_all(path.parent().unwrap());
-        let data = serde_json::json!({ "default_profile": profile });
-        return fs::write(path, serde_json::to_vec(&data).unwrap_or_default()).is_ok();
+    let path = match config_store_path() {
+        Some(p) => p,
+        None => return false,
+    };
+    let _ = fs::create_dir_all(path.parent().unwrap());
+    // Merge into the existing settings so per-profile options are kept
+    let mut root = serde_json::json!({});
+    if let Ok(existing) = fs::read_to_string(&path) {
+        if let Ok(v) = serde_json::from_str::<serde_json::Value>(&existing) {
+            if v.is_object() {
+                root = v;
+            }
+        }
     }
-    false
+    root["default_profile"] = serde_json::Value::String(profile.to_string());
+    fs::write(&path, serde_json::to_vec_pretty(&root).unwrap_or_default()).is_ok()
 }
 
 #[tauri::command]
@@ -232,3 +306,257 @@ pub fn save_recent_secrets(recent: Vec<String>) -> bool {
     }
     false
 }
+
+// ==== ExternalSecret generator options (per profile) ====
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ExternalSecretOptions {
+    pub api_version: String,
+    pub store_name: String,
+    pub store_kind: String,
+    pub refresh_interval: String,
+    pub creation_policy: String,
+    pub namespace: Option<String>,
+}
+
+impl Default for ExternalSecretOptions {
+    fn default() -> Self {
+        ExternalSecretOptions {
+            api_version: "external-secrets.io/v1beta1".to_string(),
+            store_name: "aws-cluster-secret-store".to_string(),
+            store_kind: "ClusterSecretStore".to_string(),
+            refresh_interval: "1h".to_string(),
+            creation_policy: "Owner".to_string(),
+            namespace: None,
+        }
+    }
+}
+
+fn read_settings() -> serde_json::Value {
+    config_store_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|data| serde_json::from_str::<serde_json::Value>(&data).ok())
+        .filter(|v| v.is_object())
+        .unwrap_or_else(|| serde_json::json!({}))
+}
+
+/// Store `value` under `settings[key]`, keeping every other setting.
+fn save_setting(key: &str, value: serde_json::Value) -> bool {
+    let Some(path) = config_store_path() else {
+        return false;
+    };
+    let _ = fs::create_dir_all(path.parent().unwrap());
+    let mut root = read_settings();
+    root[key] = value;
+    fs::write(&path, serde_json::to_vec_pretty(&root).unwrap_or_default()).is_ok()
+}
+
+/// Store `value` under `settings[section][profile]`, keeping every other setting.
+fn save_profile_setting(section: &str, profile: &str, value: serde_json::Value) -> bool {
+    let Some(path) = config_store_path() else {
+        return false;
+    };
+    let _ = fs::create_dir_all(path.parent().unwrap());
+    let mut root = read_settings();
+    if !root[section].is_object() {
+        root[section] = serde_json::json!({});
+    }
+    root[section][profile] = value;
+    fs::write(&path, serde_json::to_vec_pretty(&root).unwrap_or_default()).is_ok()
+}
+
+fn load_profile_setting<T: serde::de::DeserializeOwned + Default>(
+    section: &str,
+    profile: &str,
+) -> T {
+    read_settings()
+        .get(section)
+        .and_then(|v| v.get(profile))
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+pub fn load_external_secret_options(profile: &str) -> ExternalSecretOptions {
+    load_profile_setting("external_secret_options", profile)
+}
+
+#[tauri::command]
+pub fn save_external_secret_options(profile: &str, options: ExternalSecretOptions) -> bool {
+    save_profile_setting(
+        "external_secret_options",
+        profile,
+        serde_json::to_value(options).unwrap_or_default(),
+    )
+}
+
+// ==== Team policy file location ====
+/// `SECMANAGER_POLICY_FILE`, or the path saved in settings.
+pub fn policy_file_path() -> Option<PathBuf> {
+    if let Some(p) = std::env::var_os("SECMANAGER_POLICY_FILE").filter(|p| !p.is_empty()) {
+        return Some(PathBuf::from(p));
+    }
+    read_settings()
+        .get("policy_file")
+        .and_then(|v| v.as_str())
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+}
+
+#[tauri::command]
+pub fn load_policy_file_path() -> Option<String> {
+    policy_file_path().map(|p| p.display().to_string())
+}
+
+/// `None` clears the setting; the env var still wins when set.
+#[tauri::command]
+pub fn save_policy_file_path(path: Option<String>) -> bool {
+    save_setting(
+        "policy_file",
+        path.map(serde_json::Value::String).unwrap_or_default(),
+    )
+}
+
+// ==== Value schemas (JSON Schema per secret name glob) ====
+/// Schema rules kept in the app settings; writes check them together with the policy file's.
+#[tauri::command]
+pub fn load_value_schemas() -> Vec<SchemaRule> {
+    read_settings()
+        .get("value_schemas")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Rejects the whole list if any glob or schema is invalid.
+#[tauri::command]
+pub fn save_value_schemas(schemas: Vec<SchemaRule>) -> Result<bool, String> {
+    SchemaSet::compile(&schemas)?;
+    let value = serde_json::to_value(&schemas).map_err(|e| e.to_string())?;
+    Ok(save_setting("value_schemas", value))
+}
+
+// ==== Profile protection (guards writes to production profiles) ====
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+#[serde(default)]
+pub struct ProfileProtection {
+    /// Create/update/delete/restore need a confirmation token from `prepare_protected_write`
+    pub protected: bool,
+    /// Every write is rejected
+    pub read_only: bool,
+}
+
+// ==== Read-only mode (session-wide, per profile, team-managed) ====
+/// Team-managed settings, e.g. distributed by config management. Flags set here can only
+/// tighten what users choose in the app, never relax it.
+#[derive(Deserialize, Clone, Default)]
+#[serde(default)]
+struct TeamConfig {
+    read_only: bool,
+    profiles: std::collections::HashMap<String, ProfileProtection>,
+}
+
+/// `SECMANAGER_TEAM_CONFIG`, or `team.json` next to `settings.json`.
+fn team_config_path() -> Option<PathBuf> {
+    if let Some(p) = std::env::var_os("SECMANAGER_TEAM_CONFIG").filter(|p| !p.is_empty()) {
+        return Some(PathBuf::from(p));
+    }
+    let dir = dirs::config_dir()?;
+    Some(dir.join("secmanager").join("team.json"))
+}
+
+/// A missing file means no team restrictions; a file that cannot be read or parsed is an
+/// error, so callers fail closed instead of silently dropping the team's flags.
+fn load_team_config() -> Result<TeamConfig, String> {
+    let Some(path) = team_config_path() else {
+        return Ok(TeamConfig::default());
+    };
+    let data = match fs::read_to_string(&path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(TeamConfig::default()),
+        Err(e) => return Err(format!("cannot read team config {}: {e}", path.display())),
+    };
+    serde_json::from_str(&data).map_err(|e| format!("invalid team config {}: {e}", path.display()))
+}
+
+/// Effective write restrictions for a profile, combining app settings and the team config.
+#[derive(Serialize, Clone, Default, Debug)]
+pub struct WriteAccess {
+    pub protected: bool,
+    pub read_only: bool,
+    /// Why writes are blocked, when `read_only` is set
+    pub read_only_reason: Option<String>,
+    /// The team config forces `protected` or `read_only`; the app cannot turn them off
+    pub locked_by_team: bool,
+}
+
+/// Write access for a profile; `None` is the SDK default profile.
+pub fn write_access(profile: Option<&str>) -> WriteAccess {
+    let name = profile.unwrap_or("default");
+    let user = load_profile_protection(name);
+    let team = match load_team_config() {
+        Ok(team) => team,
+        Err(e) => {
+            return WriteAccess {
+                protected: true,
+                read_only: true,
+                read_only_reason: Some(format!(
+                    "{e}; all profiles are read-only until it is fixed"
+                )),
+                locked_by_team: true,
+            }
+        }
+    };
+    let team_profile = team.profiles.get(name).cloned().unwrap_or_default();
+    let session = load_read_only_mode();
+
+    let read_only_reason = if team.read_only {
+        Some("read-only mode is enforced by the team config".to_string())
+    } else if team_profile.read_only {
+        Some(format!("profile '{name}' is read-only by team config"))
+    } else if session {
+        Some("read-only mode is on".to_string())
+    } else if user.read_only {
+        Some(format!("profile '{name}' is read-only"))
+    } else {
+        None
+    };
+    WriteAccess {
+        protected: user.protected || team_profile.protected,
+        read_only: read_only_reason.is_some(),
+        read_only_reason,
+        locked_by_team: team.read_only || team_profile.read_only || team_profile.protected,
+    }
+}
+
+#[tauri::command]
+pub fn get_write_access(profile: Option<String>) -> WriteAccess {
+    write_access(profile.as_deref())
+}
+
+/// Session-wide read-only flag, applied to every profile.
+#[tauri::command]
+pub fn load_read_only_mode() -> bool {
+    read_settings()
+        .get("read_only")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+#[tauri::command]
+pub fn save_read_only_mode(enabled: bool) -> bool {
+    save_setting("read_only", serde_json::Value::Bool(enabled))
+}
+
+#[tauri::command]
+pub fn load_profile_protection(profile: &str) -> ProfileProtection {
+    load_profile_setting("profile_protection", profile)
+}
+
+#[tauri::command]
+pub fn save_profile_protection(profile: &str, protection: ProfileProtection) -> bool {
+    save_profile_setting(
+        "profile_protection",
+        profile,
+        serde_json::to_value(protection).unwrap_or_default(),
+    )
+}
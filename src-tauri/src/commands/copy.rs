@@ -0,0 +1,488 @@
+use crate::commands::aws;
+use crate::commands::bundle::{self, ImportAction};
+use crate::commands::error::CommandError;
+use crate::commands::guard::{self, ProtectedAction};
+use crate::commands::team_policy;
+use crate::helper::audit::{self, AuditAction, AuditOutcome};
+use crate::helper::aws_helper;
+use serde::{Deserialize, Serialize};
+use tauri::Emitter;
+
+// ==== Copy secrets between profiles / regions ====
+#[derive(Deserialize, Clone, PartialEq)]
+pub struct SecretLocation {
+    pub profile: Option<String>,
+    /// Falls back to the profile's region when `None`
+    pub region: Option<String>,
+}
+
+/// Replace a leading name prefix, e.g. `dev/` -> `staging/`.
+#[derive(Deserialize, Clone)]
+pub struct PrefixRename {
+    pub from: String,
+    pub to: String,
+}
+
+impl PrefixRename {
+    pub fn apply(&self, name: &str) -> Result<String, String> {
+        name.strip_prefix(&self.from)
+            .map(|rest| format!("{}{rest}", self.to))
+            .ok_or_else(|| format!("Secret '{name}' does not start with '{}'", self.from))
+    }
+}
+
+#[derive(Serialize, Clone)]
+pub struct CopyResult {
+    pub source_name: String,
+    pub target_name: String,
+    pub action: ImportAction,
+    pub success: bool,
+    pub message: String,
+}
+
+/// Copy value, description, tags and string/binary type of a secret to another profile/region.
+/// KMS keys are not carried over since they rarely exist in the target; the target default key is used.
+/// An existing target is only overwritten when `overwrite` is set.
+#[tauri::command]
+pub async fn copy_secret(
+    source: SecretLocation,
+    target: SecretLocation,
+    secret_id: String,
+    rename: Option<PrefixRename>,
+    overwrite: Option<bool>,
+    dry_run: Option<bool>,
+    confirmation_token: Option<String>,
+) -> Result<CopyResult, CommandError> {
+    let target_profile = target.profile.clone();
+    copy_one(
+        source,
+        target,
+        secret_id,
+        rename.as_ref(),
+        overwrite,
+        dry_run,
+        |target_name| {
+            guard::check_write(
+                target_profile.as_deref(),
+                ProtectedAction::Copy,
+                target_name,
+                confirmation_token.as_deref(),
+            )
+        },
+    )
+    .await
+}
+
+/// Copy several secrets with one confirmation, typed as the target profile name.
+/// Each secret is planned and applied on its own; failures do not stop the rest.
+#[tauri::command]
+pub async fn copy_secrets(
+    source: SecretLocation,
+    target: SecretLocation,
+    secret_ids: Vec<String>,
+    rename: Option<PrefixRename>,
+    overwrite: Option<bool>,
+    dry_run: Option<bool>,
+    confirmation_token: Option<String>,
+) -> Result<Vec<CopyResult>, CommandError> {
+    if dry_run != Some(true) {
+        let target_profile = target.profile.as_deref();
+        guard::check_write(
+            target_profile,
+            ProtectedAction::Copy,
+            guard::profile_label(target_profile),
+            confirmation_token.as_deref(),
+        )?;
+    }
+    let mut results = Vec::with_capacity(secret_ids.len());
+    for secret_id in secret_ids {
+        let result = copy_one(
+            source.clone(),
+            target.clone(),
+            secret_id.clone(),
+            rename.as_ref(),
+            overwrite,
+            dry_run,
+            |_| Ok(()),
+        )
+        .await;
+        // Read or plan failures are reported like conflicts: nothing was written
+        results.push(result.unwrap_or_else(|e| {
+            CopyResult {
+                target_name: rename
+                    .as_ref()
+                    .and_then(|r| r.apply(&secret_id).ok())
+                    .unwrap_or_else(|| secret_id.clone()),
+                source_name: secret_id,
+                action: ImportAction::Conflict,
+                success: false,
+                message: e.to_string(),
+            }
+        }));
+    }
+    Ok(results)
+}
+
+/// `before_write` runs with the target name once the copy is known to write.
+async fn copy_one(
+    source: SecretLocation,
+    target: SecretLocation,
+    secret_id: String,
+    rename: Option<&PrefixRename>,
+    overwrite: Option<bool>,
+    dry_run: Option<bool>,
+    before_write: impl FnOnce(&str) -> Result<(), CommandError>,
+) -> Result<CopyResult, CommandError> {
+    let target_name = match rename {
+        Some(r) => r.apply(&secret_id)?,
+        None => secret_id.clone(),
+    };
+    if source == target && target_name == secret_id {
+        return Err("Source and target are the same secret".to_string().into());
+    }
+
+    let source_client = aws_helper::secrets_client_in_region(source.profile, source.region).await;
+    let target_client =
+        aws_helper::secrets_client_in_region(target.profile.clone(), target.region).await;
+    let mut entry = bundle::read_bundle_entry(&source_client, &secret_id).await?;
+    entry.name = target_name.clone();
+
+    let plan = bundle::plan_entry(&target_client, &entry).await?;
+    let result = |action, success, message: String| CopyResult {
+        source_name: secret_id.clone(),
+        target_name: target_name.clone(),
+        action,
+        success,
+        message,
+    };
+    if plan.action == ImportAction::Update && overwrite != Some(true) {
+        return Ok(result(
+            plan.action,
+            false,
+            format!("Secret '{target_name}' already exists in the target; enable overwrite to update it"),
+        ));
+    }
+    if dry_run == Some(true) {
+        let message = plan.reason.clone().unwrap_or_else(|| "Dry run".to_string());
+        return Ok(result(
+            plan.action,
+            plan.action != ImportAction::Conflict,
+            message,
+        ));
+    }
+
+    before_write(&target_name)?;
+    let applied =
+        bundle::apply_entry(&target_client, target.profile.as_deref(), &entry, plan).await;
+    Ok(result(applied.action, applied.success, applied.message))
+}
+
+// ==== Rename / move (Secrets Manager has no native rename) ====
+#[derive(Serialize, Clone)]
+pub struct MovedSecret {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Serialize, Clone)]
+pub struct MoveFolderResult {
+    pub moved: Vec<MovedSecret>,
+    pub rolled_back: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+struct MoveProgress {
+    from: String,
+    to: String,
+    stage: &'static str,
+    done: usize,
+    total: usize,
+}
+
+/// Create `to` from `from` in the same account: value, description, tags, KMS key and
+/// resource policy. Fails if `to` already exists.
+async fn copy_to_new_name(
+    client: &aws_sdk_secretsmanager::Client,
+    profile: Option<&str>,
+    from: &str,
+    to: &str,
+) -> Result<(), String> {
+    let (mut entry, meta) = bundle::read_bundle_entry_and_meta(client, from).await?;
+    entry.name = to.to_string();
+    let plan = bundle::plan_entry(client, &entry).await?;
+    if plan.action != ImportAction::Create {
+        return Err(format!("Secret '{to}' already exists"));
+    }
+    let policy = client
+        .get_resource_policy()
+        .secret_id(from)
+        .send()
+        .await
+        .map_err(|e| aws_helper::format_sdk_error(&e, from))?
+        .resource_policy;
+
+    // Same account, so the source's key exists here; without it the copy would use the default key
+    let mut write = entry.to_write();
+    write.kms_key_id = meta.kms_key_id().map(|k| k.to_string());
+    team_policy::check_write_policy(profile, &write, None)
+        .await
+        .map_err(|e| e.to_string())?;
+    aws::create_secret_with(client, profile, write).await?;
+
+    if let Some(policy) = policy {
+        let result = client
+            .put_resource_policy()
+            .secret_id(to)
+            .resource_policy(policy)
+            .block_public_policy(true)
+            .send()
+            .await;
+        audit::record(
+            client,
+            profile,
+            AuditAction::PutResourcePolicy,
+            to,
+            AuditOutcome::from_result(&result, |r| (r.arn(), None)),
+        )
+        .await;
+        if let Err(e) = result {
+            let _ = discard_created(client, profile, to).await;
+            return Err(format!(
+                "Copying the resource policy to '{to}' failed: {}",
+                aws_helper::format_sdk_error(&e, to)
+            ));
+        }
+    }
+    Ok(())
+}
+
+async fn schedule_deletion(
+    client: &aws_sdk_secretsmanager::Client,
+    profile: Option<&str>,
+    secret_id: &str,
+    recovery_window_days: Option<i64>,
+) -> Result<(), String> {
+    let result = client
+        .delete_secret()
+        .secret_id(secret_id)
+        .set_recovery_window_in_days(recovery_window_days)
+        .send()
+        .await;
+    audit::record(
+        client,
+        profile,
+        AuditAction::Delete,
+        secret_id,
+        AuditOutcome::from_result(&result, |r| (r.arn(), None)),
+    )
+    .await;
+    result.map_err(|e| aws_helper::format_sdk_error(&e, secret_id))?;
+    Ok(())
+}
+
+/// Undo a scheduled deletion during rollback.
+async fn restore_deleted(
+    client: &aws_sdk_secretsmanager::Client,
+    profile: Option<&str>,
+    secret_id: &str,
+) -> Result<(), String> {
+    let result = client.restore_secret().secret_id(secret_id).send().await;
+    audit::record(
+        client,
+        profile,
+        AuditAction::Restore,
+        secret_id,
+        AuditOutcome::from_result(&result, |r| (r.arn(), None)),
+    )
+    .await;
+    result.map_err(|e| aws_helper::format_sdk_error(&e, secret_id))?;
+    Ok(())
+}
+
+/// Remove a secret created by a move that is being rolled back. It never held anything
+/// the old secret does not, so it is deleted without a recovery window.
+async fn discard_created(
+    client: &aws_sdk_secretsmanager::Client,
+    profile: Option<&str>,
+    secret_id: &str,
+) -> Result<(), String> {
+    let result = client
+        .delete_secret()
+        .secret_id(secret_id)
+        .force_delete_without_recovery(true)
+        .send()
+        .await;
+    audit::record(
+        client,
+        profile,
+        AuditAction::Delete,
+        secret_id,
+        AuditOutcome::from_result(&result, |r| (r.arn(), None)),
+    )
+    .await;
+    result.map_err(|e| aws_helper::format_sdk_error(&e, secret_id))?;
+    Ok(())
+}
+
+/// Rename a secret: create `new_name` with the same value, description, tags, KMS key and
+/// resource policy, then schedule deletion of the old one (recoverable with `restore_secret`).
+#[tauri::command]
+pub async fn move_secret(
+    profile: Option<String>,
+    secret_id: String,
+    new_name: String,
+    recovery_window_days: Option<i64>,
+    confirmation_token: Option<String>,
+) -> Result<String, CommandError> {
+    guard::check_write(
+        profile.as_deref(),
+        ProtectedAction::Move,
+        &secret_id,
+        confirmation_token.as_deref(),
+    )?;
+    if secret_id == new_name {
+        return Err("New name is the same as the current one".to_string().into());
+    }
+    let client = aws_helper::secrets_client(profile.clone()).await;
+    let profile = profile.as_deref();
+    copy_to_new_name(&client, profile, &secret_id, &new_name).await?;
+    if let Err(e) = schedule_deletion(&client, profile, &secret_id, recovery_window_days).await {
+        let _ = discard_created(&client, profile, &new_name).await;
+        return Err(format!("Failed to delete '{secret_id}', move rolled back: {e}").into());
+    }
+    Ok(format!("Moved secret: {secret_id} -> {new_name}"))
+}
+
+/// Move every secret under the folder `from_prefix` to `to_prefix`; both end with `/`, so
+/// `dev/` never picks up `devops/`. All copies are created first; only then are the old secrets scheduled for deletion.
+/// On any failure the already applied steps are undone and `rolled_back` is set.
+#[tauri::command]
+pub async fn move_folder(
+    app: tauri::AppHandle,
+    profile: Option<String>,
+    from_prefix: String,
+    to_prefix: String,
+    recovery_window_days: Option<i64>,
+    confirmation_token: Option<String>,
+) -> Result<MoveFolderResult, CommandError> {
+    if from_prefix == "/" || from_prefix == to_prefix {
+        return Err("Choose a non-empty source prefix different from the target"
+            .to_string()
+            .into());
+    }
+    if !from_prefix.ends_with('/') || !to_prefix.ends_with('/') {
+        return Err("Folder prefixes must end with '/'".to_string().into());
+    }
+    guard::check_write(
+        profile.as_deref(),
+        ProtectedAction::Move,
+        &from_prefix,
+        confirmation_token.as_deref(),
+    )?;
+    let rename = PrefixRename {
+        from: from_prefix.clone(),
+        to: to_prefix,
+    };
+    let names = bundle::select_secret_ids(profile.clone(), Some(from_prefix), None).await?;
+    if names.is_empty() {
+        return Err("No secrets found under the source prefix"
+            .to_string()
+            .into());
+    }
+    let pairs: Vec<MovedSecret> = names
+        .into_iter()
+        .map(|from| {
+            let to = rename.apply(&from)?;
+            Ok(MovedSecret { from, to })
+        })
+        .collect::<Result<_, String>>()?;
+
+    let client = aws_helper::secrets_client(profile.clone()).await;
+    let profile = profile.as_deref();
+    let progress = |pair: &MovedSecret, stage: &'static str, done: usize, total: usize| {
+        let _ = app.emit(
+            "move_progress",
+            MoveProgress {
+                from: pair.from.clone(),
+                to: pair.to.clone(),
+                stage,
+                done,
+                total,
+            },
+        );
+    };
+
+    // Phase 1: create every new secret
+    let mut created: Vec<&MovedSecret> = Vec::new();
+    let mut failure: Option<String> = None;
+    for (i, pair) in pairs.iter().enumerate() {
+        match copy_to_new_name(&client, profile, &pair.from, &pair.to).await {
+            Ok(()) => {
+                created.push(pair);
+                progress(pair, "copy", i + 1, pairs.len());
+            }
+            Err(e) => {
+                failure = Some(format!("Copying '{}' failed: {e}", pair.from));
+                break;
+            }
+        }
+    }
+
+    // Phase 2: schedule deletion of the old secrets
+    let mut deleted: Vec<&MovedSecret> = Vec::new();
+    if failure.is_none() {
+        for (i, pair) in pairs.iter().enumerate() {
+            match schedule_deletion(&client, profile, &pair.from, recovery_window_days).await {
+                Ok(()) => {
+                    deleted.push(pair);
+                    progress(pair, "delete", i + 1, pairs.len());
+                }
+                Err(e) => {
+                    failure = Some(format!("Deleting '{}' failed: {e}", pair.from));
+                    break;
+                }
+            }
+        }
+    }
+
+    let Some(error) = failure else {
+        return Ok(MoveFolderResult {
+            moved: pairs,
+            rolled_back: false,
+            error: None,
+        });
+    };
+
+    // Rollback: restore the old secrets, then drop the new copies; one count over both steps
+    let mut rollback_errors = Vec::new();
+    let rollback_total = deleted.len() + created.len();
+    let mut done = 0;
+    for pair in &deleted {
+        if let Err(e) = restore_deleted(&client, profile, &pair.from).await {
+            rollback_errors.push(e);
+        }
+        done += 1;
+        progress(pair, "rollback", done, rollback_total);
+    }
+    for pair in &created {
+        if let Err(e) = discard_created(&client, profile, &pair.to).await {
+            rollback_errors.push(e);
+        }
+        done += 1;
+        progress(pair, "rollback", done, rollback_total);
+    }
+    let error = if rollback_errors.is_empty() {
+        error
+    } else {
+        format!(
+            "{error}; rollback incomplete: {}",
+            rollback_errors.join("; ")
+        )
+    };
+    Ok(MoveFolderResult {
+        moved: Vec::new(),
+        rolled_back: true,
+        error: Some(error),
+    })
+}
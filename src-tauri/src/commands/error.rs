@@ -0,0 +1,57 @@
+use crate::commands::config::SecretContent;
+use crate::commands::guard::ProtectedAction;
+use crate::helper::team_policy::PolicyViolation;
+use serde::Serialize;
+
+// ==== Structured command errors ====
+// Every variant carries a `message` so the FE can keep showing `error.message`,
+// while `kind` lets it react to specific cases (e.g. open a merge view on conflict).
+#[derive(Serialize, Clone, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CommandError {
+    Failed {
+        message: String,
+    },
+    VersionConflict {
+        message: String,
+        secret_id: String,
+        expected_version_id: String,
+        current: Box<SecretContent>,
+    },
+    /// The profile is read-only; no write is attempted
+    ReadOnly {
+        message: String,
+        profile: Option<String>,
+    },
+    /// The profile is protected; call `prepare_protected_write` and retry with its token
+    ConfirmationRequired {
+        message: String,
+        profile: Option<String>,
+        action: ProtectedAction,
+        secret_id: String,
+    },
+    /// The write breaks the team policy; nothing was sent to AWS
+    PolicyViolation {
+        message: String,
+        secret_id: String,
+        violations: Vec<PolicyViolation>,
+    },
+}
+
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        CommandError::Failed { message }
+    }
+}
+
+impl std::fmt::Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommandError::Failed { message }
+            | CommandError::VersionConflict { message, .. }
+            | CommandError::ReadOnly { message, .. }
+            | CommandError::ConfirmationRequired { message, .. }
+            | CommandError::PolicyViolation { message, .. } => f.write_str(message),
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use crate::commands::aws::{self, SecretWrite};
+use crate::commands::config::SecretContent;
+use crate::commands::error::CommandError;
+use crate::commands::guard::{self, ProtectedAction};
+use crate::helper::aws_helper;
+use crate::helper::text_format::{self, TextFormat};
+
+// ==== Format conversions (.env, YAML, TOML) ====
+#[tauri::command]
+pub async fn secret_to_dotenv(
+    profile: Option<String>,
+    secret_id: String,
+) -> Result<String, String> {
+    aws::fetch_secret(profile, secret_id).await?.to_dotenv()
+}
+
+/// Preview the JSON that `.env` content would be saved as.
+#[tauri::command]
+pub fn dotenv_to_json(content: String) -> Result<String, String> {
+    SecretContent::from_dotenv(&content)?
+        .string
+        .ok_or_else(|| "Empty conversion result".to_string())
+}
+
+/// Create the JSON secret from `.env` content, or replace its value if it already exists.
+#[tauri::command]
+pub async fn save_secret_from_dotenv(
+    profile: Option<String>,
+    secret_id: String,
+    content: String,
+    description: Option<String>,
+    expected_version_id: Option<String>,
+    confirmation_token: Option<String>,
+) -> Result<String, CommandError> {
+    let value = dotenv_to_json(content)?;
+    save_json_secret(
+        profile,
+        secret_id,
+        value,
+        description,
+        expected_version_id,
+        confirmation_token,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn secret_to_text_format(
+    profile: Option<String>,
+    secret_id: String,
+    format: TextFormat,
+) -> Result<String, String> {
+    let root = aws::fetch_secret(profile, secret_id).await?.json_value()?;
+    text_format::from_json(&root, format)
+}
+
+/// Canonical (pretty) JSON for YAML/TOML content, as it would be saved.
+#[tauri::command]
+pub fn text_format_to_json(content: String, format: TextFormat) -> Result<String, String> {
+    let root = text_format::to_json(&content, format)?;
+    if !root.is_object() {
+        return Err(format!(
+            "{} content must be a mapping at the top level",
+            format.name()
+        ));
+    }
+    serde_json::to_string_pretty(&root).map_err(|e| format!("Failed to serialize secret: {e}"))
+}
+
+/// Save YAML/TOML edits: converted back to canonical JSON, then created or updated.
+#[tauri::command]
+pub async fn save_secret_from_text_format(
+    profile: Option<String>,
+    secret_id: String,
+    content: String,
+    format: TextFormat,
+    description: Option<String>,
+    expected_version_id: Option<String>,
+    confirmation_token: Option<String>,
+) -> Result<String, CommandError> {
+    let value = text_format_to_json(content, format)?;
+    save_json_secret(
+        profile,
+        secret_id,
+        value,
+        description,
+        expected_version_id,
+        confirmation_token,
+    )
+    .await
+}
+
+/// Create a string secret, or update it when it exists (with the optional version check).
+pub async fn save_json_secret(
+    profile: Option<String>,
+    secret_id: String,
+    secret_value: String,
+    description: Option<String>,
+    expected_version_id: Option<String>,
+    confirmation_token: Option<String>,
+) -> Result<String, CommandError> {
+    let client = aws_helper::secrets_client(profile.clone()).await;
+    let existing = aws::describe_if_exists(&client, &secret_id).await?;
+    let action = if existing.is_some() {
+        ProtectedAction::Update
+    } else {
+        ProtectedAction::Create
+    };
+    guard::check_write(
+        profile.as_deref(),
+        action,
+        &secret_id,
+        confirmation_token.as_deref(),
+    )?;
+    let write = SecretWrite {
+        secret_id,
+        secret_value,
+        description,
+        is_binary: false,
+        kms_key_id: None,
+        tags: Vec::new(),
+    };
+    aws::put_secret_with(
+        &client,
+        profile.as_deref(),
+        write,
+        existing.as_ref(),
+        expected_version_id,
+    )
+    .await
+    .map(|r| r.message)
+}
@@ -0,0 +1,175 @@
+use crate::commands::config;
+use crate::commands::error::CommandError;
+use crate::helper::audit::{self, AuditAction};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// ==== Protected profiles (one-time confirmation tokens) ====
+const TOKEN_TTL: Duration = Duration::from_secs(120);
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtectedAction {
+    Create,
+    Update,
+    Delete,
+    Restore,
+    Copy,
+    Move,
+    Import,
+    Rotate,
+    Policy,
+    Replicate,
+}
+
+impl ProtectedAction {
+    fn verb(self) -> &'static str {
+        match self {
+            ProtectedAction::Create => "create",
+            ProtectedAction::Update => "update",
+            ProtectedAction::Delete => "delete",
+            ProtectedAction::Restore => "restore",
+            ProtectedAction::Copy => "copy",
+            ProtectedAction::Move => "move",
+            ProtectedAction::Import => "import",
+            ProtectedAction::Rotate => "rotation change",
+            ProtectedAction::Policy => "resource policy change",
+            ProtectedAction::Replicate => "replication change",
+        }
+    }
+
+    fn audit_action(self) -> AuditAction {
+        match self {
+            ProtectedAction::Create => AuditAction::Create,
+            ProtectedAction::Update => AuditAction::Update,
+            ProtectedAction::Delete => AuditAction::Delete,
+            ProtectedAction::Restore => AuditAction::Restore,
+            ProtectedAction::Copy => AuditAction::Copy,
+            ProtectedAction::Move => AuditAction::Move,
+            ProtectedAction::Import => AuditAction::Import,
+            ProtectedAction::Rotate => AuditAction::Rotate,
+            ProtectedAction::Policy => AuditAction::ResourcePolicy,
+            ProtectedAction::Replicate => AuditAction::Replication,
+        }
+    }
+}
+
+struct PendingWrite {
+    profile: Option<String>,
+    action: ProtectedAction,
+    secret_id: String,
+    expires_at: Instant,
+}
+
+/// Issued tokens, each valid for one matching write.
+static PENDING: Mutex<Option<HashMap<String, PendingWrite>>> = Mutex::new(None);
+
+pub fn profile_label(profile: Option<&str>) -> &str {
+    profile.unwrap_or("default")
+}
+
+/// Reject any write while read-only mode applies to `profile`.
+/// Every mutating command calls this (directly or through `check_write`) before touching AWS.
+pub fn ensure_writable(profile: Option<&str>) -> Result<config::WriteAccess, CommandError> {
+    let access = config::write_access(profile);
+    match &access.read_only_reason {
+        Some(reason) => Err(CommandError::ReadOnly {
+            message: format!(
+                "Write to profile '{}' rejected: {reason}",
+                profile_label(profile)
+            ),
+            profile: profile.map(|p| p.to_string()),
+        }),
+        None => Ok(access),
+    }
+}
+
+/// Issue a token for one write to a protected profile. The caller proves intent by typing
+/// the name in `secret_id` exactly: the secret name, or for a bulk operation the source
+/// prefix (folder move) or the profile name (import, multi-secret copy).
+#[tauri::command]
+pub fn prepare_protected_write(
+    profile: Option<String>,
+    action: ProtectedAction,
+    secret_id: String,
+    typed_name: String,
+) -> Result<String, CommandError> {
+    ensure_writable(profile.as_deref())?;
+    if typed_name.trim() != secret_id {
+        return Err(format!("Typed name does not match '{secret_id}'").into());
+    }
+    let mut bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+    let token: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+
+    let mut guard = PENDING
+        .lock()
+        .map_err(|_| "Confirmation store is unavailable".to_string())?;
+    let pending = guard.get_or_insert_with(HashMap::new);
+    let now = Instant::now();
+    pending.retain(|_, p| p.expires_at > now);
+    pending.insert(
+        token.clone(),
+        PendingWrite {
+            profile,
+            action,
+            secret_id,
+            expires_at: now + TOKEN_TTL,
+        },
+    );
+    Ok(token)
+}
+
+/// Reject the write when the profile is read-only, or protected without a valid token.
+/// A matching token is consumed; bulk operations check once for the whole operation.
+/// Rejections are audited, except a missing token, which only asks for confirmation.
+pub fn check_write(
+    profile: Option<&str>,
+    action: ProtectedAction,
+    secret_id: &str,
+    token: Option<&str>,
+) -> Result<(), CommandError> {
+    let access = ensure_writable(profile).inspect_err(|_| {
+        audit::record_rejected(profile, action.audit_action(), secret_id, "ReadOnly");
+    })?;
+    if !access.protected {
+        return Ok(());
+    }
+    let confirmed = token.is_some_and(|token| {
+        let Ok(mut guard) = PENDING.lock() else {
+            return false;
+        };
+        let Some(p) = guard.as_mut().and_then(|m| m.remove(token)) else {
+            return false;
+        };
+        p.expires_at > Instant::now()
+            && p.action == action
+            && p.secret_id == secret_id
+            && p.profile.as_deref() == profile
+    });
+    if confirmed {
+        return Ok(());
+    }
+    if token.is_some() {
+        audit::record_rejected(
+            profile,
+            action.audit_action(),
+            secret_id,
+            "InvalidConfirmation",
+        );
+    }
+    Err(CommandError::ConfirmationRequired {
+        message: format!(
+            "Profile '{}' is protected; type '{secret_id}' to confirm the {}",
+            profile_label(profile),
+            action.verb()
+        ),
+        profile: profile.map(|p| p.to_string()),
+        action,
+        secret_id: secret_id.to_string(),
+    })
+}
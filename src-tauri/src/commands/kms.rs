@@ -0,0 +1,114 @@
+use crate::helper::aws_helper;
+use aws_smithy_types::error::metadata::ProvideErrorMetadata;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Key ARN per `(profile, key reference)`. `None` records a lookup that failed, so it is
+/// not retried on every policy check.
+type KeyArnCache = HashMap<(String, String), Option<String>>;
+static KEY_ARNS: Mutex<Option<KeyArnCache>> = Mutex::new(None);
+
+// ==== KMS keys for secret encryption ====
+#[derive(Serialize, Clone)]
+pub struct KmsAlias {
+    pub alias_name: String,
+    pub alias_arn: Option<String>,
+    pub target_key_id: Option<String>,
+    /// AWS managed aliases (`alias/aws/...`) cannot be used for cross-account access
+    pub aws_managed: bool,
+}
+
+#[tauri::command]
+pub async fn list_kms_aliases(profile: Option<String>) -> Result<Vec<KmsAlias>, String> {
+    let config = aws_helper::load_sdk_config(profile).await;
+    let client = aws_sdk_kms::Client::new(&config);
+
+    let mut out = Vec::new();
+    let mut marker: Option<String> = None;
+    loop {
+        let resp = client
+            .list_aliases()
+            .limit(100)
+            .set_marker(marker)
+            .send()
+            .await
+            .map_err(|e| match e.as_service_error() {
+                Some(se) => format!(
+                    "{}: {}",
+                    se.code().unwrap_or(""),
+                    se.message().unwrap_or("Unknown service error")
+                ),
+                None => format!("Failed to list KMS aliases: {e:?}"),
+            })?;
+        for a in resp.aliases() {
+            // Aliases without a target key cannot encrypt anything
+            if a.target_key_id().is_none() {
+                continue;
+            }
+            if let Some(name) = a.alias_name() {
+                out.push(KmsAlias {
+                    alias_name: name.to_string(),
+                    alias_arn: a.alias_arn().map(|s| s.to_string()),
+                    target_key_id: a.target_key_id().map(|s| s.to_string()),
+                    aws_managed: name.starts_with("alias/aws/"),
+                });
+            }
+        }
+        marker = resp.next_marker().map(|s| s.to_string());
+        if !resp.truncated() || marker.is_none() {
+            break;
+        }
+    }
+    Ok(out)
+}
+
+/// Resolve key ids, key ARNs, aliases and alias ARNs to the key ARN with DescribeKey, at most
+/// once per profile and reference. References that cannot be described are left out.
+pub async fn resolve_key_arns(profile: Option<&str>, keys: &[&str]) -> HashMap<String, String> {
+    let profile_key = profile.unwrap_or("default").to_string();
+    let cached = |key: &str| {
+        KEY_ARNS.lock().ok().and_then(|guard| {
+            guard
+                .as_ref()
+                .and_then(|m| m.get(&(profile_key.clone(), key.to_string())).cloned())
+        })
+    };
+
+    let mut out = HashMap::new();
+    let mut missing = Vec::new();
+    for &key in keys {
+        match cached(key) {
+            Some(Some(arn)) => {
+                out.insert(key.to_string(), arn);
+            }
+            Some(None) => {}
+            None => missing.push(key),
+        }
+    }
+    if missing.is_empty() {
+        return out;
+    }
+
+    let config = aws_helper::load_sdk_config(profile.map(|p| p.to_string())).await;
+    let client = aws_sdk_kms::Client::new(&config);
+    for key in missing {
+        let arn = client
+            .describe_key()
+            .key_id(key)
+            .send()
+            .await
+            .ok()
+            .and_then(|r| r.key_metadata)
+            .and_then(|m| m.arn);
+        if let Ok(mut guard) = KEY_ARNS.lock() {
+            guard
+                .get_or_insert_with(HashMap::new)
+                .insert((profile_key.clone(), key.to_string()), arn.clone());
+        }
+        if let Some(arn) = arn {
+            out.insert(key.to_string(), arn);
+        }
+    }
+    out
+}
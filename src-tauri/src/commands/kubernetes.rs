@@ -0,0 +1,339 @@
+use crate::commands::aws;
+use crate::commands::config::{self, ExternalSecretOptions};
+use base64::Engine as _;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+// ==== Kubernetes manifests ====
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ObjectMeta {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    namespace: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    labels: BTreeMap<String, String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct K8sSecret {
+    api_version: &'static str,
+    kind: &'static str,
+    metadata: ObjectMeta,
+    #[serde(rename = "type")]
+    secret_type: &'static str,
+    data: Map<String, Value>,
+}
+
+/// Last segment of a Secrets Manager name, made a valid DNS-1123 resource name.
+pub fn resource_name(secret_id: &str) -> String {
+    let last = secret_id
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or(secret_id);
+    let mut name: String = last
+        .to_ascii_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    name.truncate(253);
+    let name = name
+        .trim_matches(|c: char| !c.is_ascii_alphanumeric())
+        .to_string();
+    if name.is_empty() {
+        "secret".to_string()
+    } else {
+        name
+    }
+}
+
+/// Secret data keys may only contain alphanumerics, `-`, `_` and `.`.
+pub fn is_valid_data_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+}
+
+/// Emit a native `v1/Secret` for one-off debugging in dev clusters.
+/// JSON objects become one data entry per top-level key; binary and plain string
+/// values become a single entry named `data_key` (default: the resource name).
+#[tauri::command]
+pub async fn generate_k8s_secret_manifest(
+    profile: Option<String>,
+    secret_id: String,
+    name: Option<String>,
+    namespace: Option<String>,
+    labels: Option<BTreeMap<String, String>>,
+    data_key: Option<String>,
+) -> Result<String, String> {
+    let content = aws::fetch_secret(profile, secret_id.clone()).await?;
+    let b64 = |bytes: &[u8]| Value::String(base64::engine::general_purpose::STANDARD.encode(bytes));
+    let single_key = data_key.unwrap_or_else(|| resource_name(&secret_id));
+
+    let mut data = Map::new();
+    match (&content.binary_base64, content.json_value()) {
+        // Already base64: the binary payload goes through unchanged
+        (Some(bin), _) => {
+            data.insert(single_key, Value::String(bin.clone()));
+        }
+        (None, Ok(Value::Object(map))) => {
+            for (key, value) in map {
+                let raw = match value {
+                    Value::String(s) => s,
+                    other => other.to_string(),
+                };
+                data.insert(key, b64(raw.as_bytes()));
+            }
+        }
+        (None, _) => {
+            data.insert(
+                single_key,
+                b64(content.string.unwrap_or_default().as_bytes()),
+            );
+        }
+    }
+
+    let invalid: Vec<&str> = data
+        .keys()
+        .filter(|k| !is_valid_data_key(k))
+        .map(|k| k.as_str())
+        .collect();
+    if !invalid.is_empty() {
+        return Err(format!(
+            "Keys are not valid Kubernetes Secret data keys: {}",
+            invalid.join(", ")
+        ));
+    }
+
+    let manifest = K8sSecret {
+        api_version: "v1",
+        kind: "Secret",
+        metadata: ObjectMeta {
+            name: name.unwrap_or_else(|| resource_name(&secret_id)),
+            namespace: namespace.filter(|n| !n.is_empty()),
+            labels: labels.unwrap_or_default(),
+        },
+        secret_type: "Opaque",
+        data,
+    };
+    serde_norway::to_string(&manifest).map_err(|e| format!("Failed to render manifest: {e}"))
+}
+
+// ==== ExternalSecret (external-secrets.io) ====
+/// Map one JSON property of the remote secret to a key in the generated Secret.
+#[derive(Deserialize, Clone)]
+pub struct KeyMapping {
+    pub secret_key: String,
+    /// JSON property in the remote secret; defaults to `secret_key`
+    pub property: Option<String>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct ExternalSecretItem {
+    pub secret_id: String,
+    #[serde(default)]
+    pub is_binary: bool,
+    pub target_name: Option<String>,
+    /// Key used for binary secrets, usually the imported file name
+    pub binary_file_name: Option<String>,
+    /// Empty means "extract every key" (`dataFrom.extract`)
+    #[serde(default)]
+    pub key_mappings: Vec<KeyMapping>,
+    /// `target.template.data` entries, e.g. `DATABASE_URL: "postgres://{{ .user }}@..."`
+    pub template: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExternalSecret {
+    api_version: String,
+    kind: &'static str,
+    metadata: ObjectMeta,
+    spec: ExternalSecretSpec,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExternalSecretSpec {
+    refresh_interval: String,
+    secret_store_ref: StoreRef,
+    target: Target,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    data: Vec<DataEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    data_from: Vec<DataFrom>,
+}
+
+#[derive(Serialize)]
+struct StoreRef {
+    name: String,
+    kind: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Target {
+    name: String,
+    creation_policy: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    template: Option<TargetTemplate>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TargetTemplate {
+    engine_version: &'static str,
+    data: BTreeMap<String, String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DataEntry {
+    secret_key: String,
+    remote_ref: RemoteRef,
+}
+
+#[derive(Serialize)]
+struct RemoteRef {
+    key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    property: Option<String>,
+}
+
+#[derive(Serialize)]
+struct DataFrom {
+    extract: RemoteRef,
+}
+
+/// Key for a binary secret when no file name is known:
+/// drop a `-secret` suffix, keep the last three `-` parts and default to `.txt`.
+fn binary_secret_key(secret_id: &str, file_name: Option<&str>) -> String {
+    if let Some(f) = file_name.filter(|f| !f.is_empty()) {
+        return f.to_string();
+    }
+    let last = secret_id.rsplit('/').next().unwrap_or(secret_id);
+    let key = last.strip_suffix("-secret").unwrap_or(last);
+    let parts: Vec<&str> = key.split('-').collect();
+    let key = if parts.len() > 2 {
+        parts[parts.len() - 3..].join("-")
+    } else {
+        key.to_string()
+    };
+    if key.contains('.') {
+        key
+    } else {
+        format!("{key}.txt")
+    }
+}
+
+fn build_external_secret(item: ExternalSecretItem, opts: &ExternalSecretOptions) -> ExternalSecret {
+    let base = resource_name(&item.secret_id);
+    let target_name = item.target_name.unwrap_or_else(|| {
+        if item.is_binary {
+            format!("{base}-secret")
+        } else {
+            base.clone()
+        }
+    });
+
+    let (data, data_from) = if item.is_binary {
+        let key = binary_secret_key(&item.secret_id, item.binary_file_name.as_deref());
+        (
+            vec![DataEntry {
+                secret_key: key,
+                remote_ref: RemoteRef {
+                    key: item.secret_id.clone(),
+                    property: None,
+                },
+            }],
+            Vec::new(),
+        )
+    } else if item.key_mappings.is_empty() {
+        (
+            Vec::new(),
+            vec![DataFrom {
+                extract: RemoteRef {
+                    key: item.secret_id.clone(),
+                    property: None,
+                },
+            }],
+        )
+    } else {
+        let data = item
+            .key_mappings
+            .into_iter()
+            .map(|m| DataEntry {
+                remote_ref: RemoteRef {
+                    key: item.secret_id.clone(),
+                    property: Some(m.property.unwrap_or_else(|| m.secret_key.clone())),
+                },
+                secret_key: m.secret_key,
+            })
+            .collect();
+        (data, Vec::new())
+    };
+
+    ExternalSecret {
+        api_version: opts.api_version.clone(),
+        kind: "ExternalSecret",
+        metadata: ObjectMeta {
+            name: format!("{base}-es"),
+            namespace: opts.namespace.clone().filter(|n| !n.is_empty()),
+            labels: BTreeMap::new(),
+        },
+        spec: ExternalSecretSpec {
+            refresh_interval: opts.refresh_interval.clone(),
+            secret_store_ref: StoreRef {
+                name: opts.store_name.clone(),
+                kind: opts.store_kind.clone(),
+            },
+            target: Target {
+                name: target_name,
+                creation_policy: opts.creation_policy.clone(),
+                template: item
+                    .template
+                    .filter(|t| !t.is_empty())
+                    .map(|data| TargetTemplate {
+                        engine_version: "v2",
+                        data,
+                    }),
+            },
+            data,
+            data_from,
+        },
+    }
+}
+
+/// Render one ExternalSecret per item as a multi-document YAML.
+/// When `options` is not given, the options saved for `profile` are used.
+#[tauri::command]
+pub fn generate_external_secrets(
+    profile: Option<String>,
+    items: Vec<ExternalSecretItem>,
+    options: Option<ExternalSecretOptions>,
+) -> Result<String, String> {
+    if items.is_empty() {
+        return Err("Select at least one secret".to_string());
+    }
+    let opts = options.unwrap_or_else(|| {
+        config::load_external_secret_options(profile.as_deref().unwrap_or("default"))
+    });
+    let docs = items
+        .into_iter()
+        .map(|item| {
+            serde_norway::to_string(&build_external_secret(item, &opts))
+                .map_err(|e| format!("Failed to render ExternalSecret: {e}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(docs.join("---\n"))
+}
@@ -0,0 +1,135 @@
+use crate::commands::bundle::{self, BundleItemError};
+use crate::helper::aws_helper;
+use crate::helper::lint::{self, LintFinding, LintRule, Severity};
+use serde::Serialize;
+use std::collections::HashMap;
+use tauri::Emitter;
+
+// ==== Secret linting ====
+#[derive(Serialize, Clone)]
+pub struct LintReport {
+    pub scanned: usize,
+    pub findings: Vec<LintFinding>,
+    pub failed: Vec<BundleItemError>,
+}
+
+#[derive(Serialize, Clone)]
+struct LintProgress {
+    secret_id: String,
+    done: usize,
+    total: usize,
+}
+
+/// Lint the AWSCURRENT value of each secret. Values stay in memory only for the check;
+/// reuse across secrets is detected by SHA-256 digest.
+pub async fn lint_secrets_with(
+    profile: Option<String>,
+    ids: Vec<String>,
+    mut on_progress: impl FnMut(&str, usize, usize),
+) -> LintReport {
+    let client = aws_helper::secrets_client(profile).await;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default();
+    let total = ids.len();
+    let mut report = LintReport {
+        scanned: 0,
+        findings: Vec::new(),
+        failed: Vec::new(),
+    };
+    let mut seen: HashMap<[u8; 32], Vec<(String, String)>> = HashMap::new();
+
+    for (i, secret_id) in ids.iter().enumerate() {
+        match client.get_secret_value().secret_id(secret_id).send().await {
+            Err(e) => report.failed.push(BundleItemError {
+                secret_id: secret_id.clone(),
+                error: aws_helper::format_sdk_error(&e, secret_id),
+            }),
+            Ok(resp) => {
+                report.scanned += 1;
+                let mut push = |path: String, (rule, severity, message)| {
+                    report.findings.push(LintFinding {
+                        secret_id: secret_id.clone(),
+                        path,
+                        rule,
+                        severity,
+                        message,
+                    })
+                };
+                if let Some(text) = resp.secret_string() {
+                    for leaf in lint::leaves(text) {
+                        for finding in lint::lint_leaf(&leaf, now) {
+                            push(leaf.path.clone(), finding);
+                        }
+                        if lint::is_duplicate_candidate(&leaf) {
+                            seen.entry(lint::value_digest(&leaf.value))
+                                .or_default()
+                                .push((secret_id.clone(), leaf.path));
+                        }
+                    }
+                } else if let Some(blob) = resp.secret_binary() {
+                    let bytes = blob.as_ref();
+                    match std::str::from_utf8(bytes) {
+                        Ok(text) => lint::lint_pem(text, now)
+                            .into_iter()
+                            .for_each(|f| push(String::new(), f)),
+                        Err(_) => {
+                            if let Some(f) = lint::lint_certificate_der(bytes, now) {
+                                push(String::new(), f);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        on_progress(secret_id, i + 1, total);
+    }
+
+    for locations in seen.into_values() {
+        let secrets: Vec<&str> = locations.iter().map(|(id, _)| id.as_str()).collect();
+        if secrets.iter().all(|id| *id == secrets[0]) {
+            continue;
+        }
+        for (secret_id, path) in &locations {
+            let others: Vec<String> = locations
+                .iter()
+                .filter(|(id, _)| id != secret_id)
+                .map(|(id, p)| format!("{id}#{p}"))
+                .collect();
+            report.findings.push(LintFinding {
+                secret_id: secret_id.clone(),
+                path: path.clone(),
+                rule: LintRule::DuplicateValue,
+                severity: Severity::Warning,
+                message: format!("Same value is used in {}", others.join(", ")),
+            });
+        }
+    }
+    report
+        .findings
+        .sort_by(|a, b| (&a.secret_id, &a.path).cmp(&(&b.secret_id, &b.path)));
+    report
+}
+
+/// Lint the given secrets, or every secret under `prefix`.
+#[tauri::command]
+pub async fn lint_secrets(
+    app: tauri::AppHandle,
+    profile: Option<String>,
+    prefix: Option<String>,
+    secret_ids: Option<Vec<String>>,
+) -> Result<LintReport, String> {
+    let ids = bundle::select_secret_ids(profile.clone(), prefix, secret_ids).await?;
+    Ok(lint_secrets_with(profile, ids, |secret_id, done, total| {
+        let _ = app.emit(
+            "lint_progress",
+            LintProgress {
+                secret_id: secret_id.to_string(),
+                done,
+                total,
+            },
+        );
+    })
+    .await)
+}
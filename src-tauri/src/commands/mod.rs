@@ -1,3 +1,19 @@
+pub mod audit;
 pub mod aws;
+pub mod bundle;
+pub mod certificates;
 pub mod config;
+pub mod copy;
+pub mod error;
+pub mod formats;
+pub mod guard;
+pub mod kms;
+pub mod kubernetes;
+pub mod lint;
+pub mod policy;
+pub mod replication;
+pub mod rotation;
+pub mod schema;
+pub mod team_policy;
+pub mod terraform;
 pub mod window;
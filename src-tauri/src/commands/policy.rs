@@ -0,0 +1,168 @@
+use crate::commands::error::CommandError;
+use crate::commands::guard::{self, ProtectedAction};
+use crate::helper::audit::{self, AuditAction, AuditOutcome};
+use crate::helper::aws_helper::{self, format_sdk_error};
+use aws_sdk_secretsmanager::operation::put_resource_policy::PutResourcePolicyError;
+use aws_smithy_runtime_api::client::{orchestrator::HttpResponse, result::SdkError};
+use aws_smithy_types::error::metadata::ProvideErrorMetadata;
+use serde::Serialize;
+
+// ==== Resource policy (view, edit, delete, validate) ====
+#[derive(Serialize, Clone)]
+pub struct PolicyFinding {
+    pub check_name: Option<String>,
+    pub error_message: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct PolicyValidation {
+    pub passed: bool,
+    pub findings: Vec<PolicyFinding>,
+}
+
+/// Returns the policy pretty-printed, or `None` when the secret has no policy attached.
+#[tauri::command]
+pub async fn get_resource_policy(
+    profile: Option<String>,
+    secret_id: String,
+) -> Result<Option<String>, String> {
+    let client = aws_helper::secrets_client(profile).await;
+    let resp = client
+        .get_resource_policy()
+        .secret_id(&secret_id)
+        .send()
+        .await
+        .map_err(|e| format_sdk_error(&e, &secret_id))?;
+    Ok(resp.resource_policy().map(|p| {
+        serde_json::from_str::<serde_json::Value>(p)
+            .and_then(|v| serde_json::to_string_pretty(&v))
+            .unwrap_or_else(|_| p.to_string())
+    }))
+}
+
+#[tauri::command]
+pub async fn put_resource_policy(
+    profile: Option<String>,
+    secret_id: String,
+    policy: String,
+    block_public_policy: Option<bool>,
+    confirmation_token: Option<String>,
+) -> Result<String, CommandError> {
+    guard::check_write(
+        profile.as_deref(),
+        ProtectedAction::Policy,
+        &secret_id,
+        confirmation_token.as_deref(),
+    )?;
+    serde_json::from_str::<serde_json::Value>(&policy)
+        .map_err(|e| format!("Policy is not valid JSON: {e}"))?;
+    let client = aws_helper::secrets_client(profile.clone()).await;
+    let resp = client
+        .put_resource_policy()
+        .secret_id(&secret_id)
+        .resource_policy(policy)
+        .block_public_policy(block_public_policy.unwrap_or(true))
+        .send()
+        .await;
+    audit::record(
+        &client,
+        profile.as_deref(),
+        AuditAction::PutResourcePolicy,
+        &secret_id,
+        AuditOutcome::from_result(&resp, |r| (r.arn(), None)),
+    )
+    .await;
+    let resp = resp.map_err(|e| format_put_policy_error(&e, &secret_id))?;
+    Ok(format!(
+        "Updated resource policy for secret: {}",
+        resp.name().unwrap_or("unknown")
+    ))
+}
+
+#[tauri::command]
+pub async fn delete_resource_policy(
+    profile: Option<String>,
+    secret_id: String,
+    confirmation_token: Option<String>,
+) -> Result<String, CommandError> {
+    guard::check_write(
+        profile.as_deref(),
+        ProtectedAction::Policy,
+        &secret_id,
+        confirmation_token.as_deref(),
+    )?;
+    let client = aws_helper::secrets_client(profile.clone()).await;
+    let resp = client
+        .delete_resource_policy()
+        .secret_id(&secret_id)
+        .send()
+        .await;
+    audit::record(
+        &client,
+        profile.as_deref(),
+        AuditAction::DeleteResourcePolicy,
+        &secret_id,
+        AuditOutcome::from_result(&resp, |r| (r.arn(), None)),
+    )
+    .await;
+    let resp = resp.map_err(|e| format_sdk_error(&e, &secret_id))?;
+    Ok(format!(
+        "Deleted resource policy for secret: {}",
+        resp.name().unwrap_or("unknown")
+    ))
+}
+
+/// Run Secrets Manager policy validation (IAM Access Analyzer checks) without saving.
+/// `secret_id` is optional; when given, the policy is also checked against that secret.
+#[tauri::command]
+pub async fn validate_resource_policy(
+    profile: Option<String>,
+    secret_id: Option<String>,
+    policy: String,
+) -> Result<PolicyValidation, String> {
+    serde_json::from_str::<serde_json::Value>(&policy)
+        .map_err(|e| format!("Policy is not valid JSON: {e}"))?;
+    let client = aws_helper::secrets_client(profile).await;
+    let resp = client
+        .validate_resource_policy()
+        .set_secret_id(secret_id.clone())
+        .resource_policy(policy)
+        .send()
+        .await
+        .map_err(|e| format_sdk_error(&e, secret_id.as_deref().unwrap_or("policy")))?;
+    let findings = resp
+        .validation_errors()
+        .iter()
+        .map(|f| PolicyFinding {
+            check_name: f.check_name().map(|s| s.to_string()),
+            error_message: f.error_message().map(|s| s.to_string()),
+        })
+        .collect();
+    Ok(PolicyValidation {
+        passed: resp.policy_validation_passed(),
+        findings,
+    })
+}
+
+fn format_put_policy_error(
+    e: &SdkError<PutResourcePolicyError, HttpResponse>,
+    secret_id: &str,
+) -> String {
+    if let SdkError::ServiceError(se) = e {
+        let err = se.err();
+        match err.code().unwrap_or("") {
+            "PublicPolicyException" => {
+                return "Policy grants public access and was blocked (block_public_policy is on)"
+                    .to_string()
+            }
+            "MalformedPolicyDocumentException" => {
+                return format!(
+                    "Malformed policy document: {}",
+                    err.message().unwrap_or("check the policy syntax")
+                )
+            }
+            _ => {}
+        }
+    }
+    format_sdk_error(e, secret_id)
+}
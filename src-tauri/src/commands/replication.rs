@@ -0,0 +1,187 @@
+use crate::commands::config::ReplicaStatus;
+use crate::commands::error::CommandError;
+use crate::commands::guard::{self, ProtectedAction};
+use crate::helper::audit::{self, AuditAction, AuditOutcome};
+use crate::helper::aws_helper::{self, format_date, format_sdk_error};
+use aws_sdk_secretsmanager::types::{ReplicaRegionType, ReplicationStatusType};
+use serde::{Deserialize, Serialize};
+
+// ==== Multi-region replication ====
+impl From<&ReplicationStatusType> for ReplicaStatus {
+    fn from(r: &ReplicationStatusType) -> Self {
+        ReplicaStatus {
+            region: r.region().unwrap_or_default().to_string(),
+            status: r.status().map(|s| s.as_str().to_string()),
+            status_message: r.status_message().map(|s| s.to_string()),
+            kms_key_id: r.kms_key_id().map(|s| s.to_string()),
+            last_accessed_date: r.last_accessed_date().and_then(format_date),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone)]
+pub struct ReplicaRegion {
+    pub region: String,
+    /// Key in the replica region; the default `aws/secretsmanager` key there when `None`
+    pub kms_key_id: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ReplicationInfo {
+    pub secret_id: String,
+    pub primary_region: Option<String>,
+    /// Region the profile resolved to, so the FE can tell whether it is looking at a replica
+    pub current_region: Option<String>,
+    pub replicas: Vec<ReplicaStatus>,
+}
+
+#[tauri::command]
+pub async fn get_replication_status(
+    profile: Option<String>,
+    secret_id: String,
+) -> Result<ReplicationInfo, String> {
+    let config = aws_helper::load_sdk_config(profile).await;
+    let client = aws_sdk_secretsmanager::Client::new(&config);
+    let resp = client
+        .describe_secret()
+        .secret_id(&secret_id)
+        .send()
+        .await
+        .map_err(|e| format_sdk_error(&e, &secret_id))?;
+    Ok(ReplicationInfo {
+        secret_id: resp.name().unwrap_or(&secret_id).to_string(),
+        primary_region: resp.primary_region().map(|s| s.to_string()),
+        current_region: config.region().map(|r| r.to_string()),
+        replicas: resp
+            .replication_status()
+            .iter()
+            .map(ReplicaStatus::from)
+            .collect(),
+    })
+}
+
+#[tauri::command]
+pub async fn replicate_secret_to_regions(
+    profile: Option<String>,
+    secret_id: String,
+    regions: Vec<ReplicaRegion>,
+    force_overwrite: Option<bool>,
+    confirmation_token: Option<String>,
+) -> Result<Vec<ReplicaStatus>, CommandError> {
+    guard::check_write(
+        profile.as_deref(),
+        ProtectedAction::Replicate,
+        &secret_id,
+        confirmation_token.as_deref(),
+    )?;
+    if regions.is_empty() {
+        return Err("Select at least one region to replicate to"
+            .to_string()
+            .into());
+    }
+    let client = aws_helper::secrets_client(profile.clone()).await;
+    let replicas: Vec<ReplicaRegionType> = regions
+        .into_iter()
+        .map(|r| {
+            ReplicaRegionType::builder()
+                .region(r.region)
+                .set_kms_key_id(r.kms_key_id)
+                .build()
+        })
+        .collect();
+    let resp = client
+        .replicate_secret_to_regions()
+        .secret_id(&secret_id)
+        .set_add_replica_regions(Some(replicas))
+        .force_overwrite_replica_secret(force_overwrite.unwrap_or(false))
+        .send()
+        .await;
+    audit::record(
+        &client,
+        profile.as_deref(),
+        AuditAction::AddReplica,
+        &secret_id,
+        AuditOutcome::from_result(&resp, |r| (r.arn(), None)),
+    )
+    .await;
+    let resp = resp.map_err(|e| format_sdk_error(&e, &secret_id))?;
+    Ok(resp
+        .replication_status()
+        .iter()
+        .map(ReplicaStatus::from)
+        .collect())
+}
+
+#[tauri::command]
+pub async fn remove_replica_regions(
+    profile: Option<String>,
+    secret_id: String,
+    regions: Vec<String>,
+    confirmation_token: Option<String>,
+) -> Result<Vec<ReplicaStatus>, CommandError> {
+    guard::check_write(
+        profile.as_deref(),
+        ProtectedAction::Replicate,
+        &secret_id,
+        confirmation_token.as_deref(),
+    )?;
+    if regions.is_empty() {
+        return Err("Select at least one region to remove".to_string().into());
+    }
+    let client = aws_helper::secrets_client(profile.clone()).await;
+    let resp = client
+        .remove_regions_from_replication()
+        .secret_id(&secret_id)
+        .set_remove_replica_regions(Some(regions))
+        .send()
+        .await;
+    audit::record(
+        &client,
+        profile.as_deref(),
+        AuditAction::RemoveReplica,
+        &secret_id,
+        AuditOutcome::from_result(&resp, |r| (r.arn(), None)),
+    )
+    .await;
+    let resp = resp.map_err(|e| format_sdk_error(&e, &secret_id))?;
+    Ok(resp
+        .replication_status()
+        .iter()
+        .map(ReplicaStatus::from)
+        .collect())
+}
+
+/// Promote a replica to a standalone secret. The call has to be made in the replica's region.
+#[tauri::command]
+pub async fn promote_replica(
+    profile: Option<String>,
+    secret_id: String,
+    region: String,
+    confirmation_token: Option<String>,
+) -> Result<String, CommandError> {
+    guard::check_write(
+        profile.as_deref(),
+        ProtectedAction::Replicate,
+        &secret_id,
+        confirmation_token.as_deref(),
+    )?;
+    let client = aws_helper::secrets_client_in_region(profile.clone(), Some(region.clone())).await;
+    let resp = client
+        .stop_replication_to_replica()
+        .secret_id(&secret_id)
+        .send()
+        .await;
+    audit::record(
+        &client,
+        profile.as_deref(),
+        AuditAction::PromoteReplica,
+        &secret_id,
+        AuditOutcome::from_result(&resp, |r| (r.arn(), None)),
+    )
+    .await;
+    let resp = resp.map_err(|e| format_sdk_error(&e, &secret_id))?;
+    Ok(format!(
+        "Promoted replica in {region} to standalone secret: {}",
+        resp.arn().unwrap_or("unknown")
+    ))
+}
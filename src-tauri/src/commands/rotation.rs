@@ -0,0 +1,177 @@
+use crate::commands::error::CommandError;
+use crate::commands::guard::{self, ProtectedAction};
+use crate::helper::audit::{self, AuditAction, AuditOutcome};
+use crate::helper::aws_helper::{self, format_date, format_sdk_error};
+use aws_sdk_secretsmanager::types::RotationRulesType;
+use serde::{Deserialize, Serialize};
+
+// ==== Rotation (status, configure, rotate now, cancel) ====
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct RotationRules {
+    pub automatically_after_days: Option<i64>,
+    pub duration: Option<String>,
+    pub schedule_expression: Option<String>,
+}
+
+impl RotationRules {
+    fn from_aws(rules: &RotationRulesType) -> Self {
+        RotationRules {
+            automatically_after_days: rules.automatically_after_days(),
+            duration: rules.duration().map(|s| s.to_string()),
+            schedule_expression: rules.schedule_expression().map(|s| s.to_string()),
+        }
+    }
+
+    fn to_aws(&self) -> RotationRulesType {
+        RotationRulesType::builder()
+            .set_automatically_after_days(self.automatically_after_days)
+            .set_duration(self.duration.clone())
+            .set_schedule_expression(self.schedule_expression.clone())
+            .build()
+    }
+}
+
+#[derive(Serialize, Clone)]
+pub struct RotationStatus {
+    pub secret_id: String,
+    pub rotation_enabled: bool,
+    pub rotation_lambda_arn: Option<String>,
+    pub rotation_rules: Option<RotationRules>,
+    pub last_rotated_date: Option<String>,
+    pub next_rotation_date: Option<String>,
+}
+
+#[tauri::command]
+pub async fn get_rotation_status(
+    profile: Option<String>,
+    secret_id: String,
+) -> Result<RotationStatus, String> {
+    let client = aws_helper::secrets_client(profile).await;
+    let resp = client
+        .describe_secret()
+        .secret_id(&secret_id)
+        .send()
+        .await
+        .map_err(|e| format_sdk_error(&e, &secret_id))?;
+    Ok(RotationStatus {
+        secret_id: resp.name().unwrap_or(&secret_id).to_string(),
+        rotation_enabled: resp.rotation_enabled().unwrap_or(false),
+        rotation_lambda_arn: resp.rotation_lambda_arn().map(|s| s.to_string()),
+        rotation_rules: resp.rotation_rules().map(RotationRules::from_aws),
+        last_rotated_date: resp.last_rotated_date().and_then(format_date),
+        next_rotation_date: resp.next_rotation_date().and_then(format_date),
+    })
+}
+
+/// Enable or change rotation. A `lambda_arn` is only needed for Lambda-based rotation;
+/// managed rotation (e.g. RDS-managed secrets) only takes the rules.
+#[tauri::command]
+pub async fn configure_rotation(
+    profile: Option<String>,
+    secret_id: String,
+    rules: RotationRules,
+    lambda_arn: Option<String>,
+    rotate_immediately: Option<bool>,
+    confirmation_token: Option<String>,
+) -> Result<String, CommandError> {
+    guard::check_write(
+        profile.as_deref(),
+        ProtectedAction::Rotate,
+        &secret_id,
+        confirmation_token.as_deref(),
+    )?;
+    if rules.automatically_after_days.is_none() && rules.schedule_expression.is_none() {
+        return Err(
+            "Rotation rules need either a number of days or a schedule expression"
+                .to_string()
+                .into(),
+        );
+    }
+    let client = aws_helper::secrets_client(profile.clone()).await;
+    let resp = client
+        .rotate_secret()
+        .secret_id(&secret_id)
+        .rotation_rules(rules.to_aws())
+        .set_rotation_lambda_arn(lambda_arn)
+        .rotate_immediately(rotate_immediately.unwrap_or(false))
+        .send()
+        .await;
+    audit::record(
+        &client,
+        profile.as_deref(),
+        AuditAction::Rotate,
+        &secret_id,
+        AuditOutcome::from_result(&resp, |r| (r.arn(), r.version_id())),
+    )
+    .await;
+    let resp = resp.map_err(|e| format_sdk_error(&e, &secret_id))?;
+    Ok(format!(
+        "Configured rotation for secret: {}",
+        resp.name().unwrap_or("unknown")
+    ))
+}
+
+/// Trigger rotation now using the already configured rules and Lambda.
+#[tauri::command]
+pub async fn rotate_secret_now(
+    profile: Option<String>,
+    secret_id: String,
+    confirmation_token: Option<String>,
+) -> Result<String, CommandError> {
+    guard::check_write(
+        profile.as_deref(),
+        ProtectedAction::Rotate,
+        &secret_id,
+        confirmation_token.as_deref(),
+    )?;
+    let client = aws_helper::secrets_client(profile.clone()).await;
+    let resp = client.rotate_secret().secret_id(&secret_id).send().await;
+    audit::record(
+        &client,
+        profile.as_deref(),
+        AuditAction::Rotate,
+        &secret_id,
+        AuditOutcome::from_result(&resp, |r| (r.arn(), r.version_id())),
+    )
+    .await;
+    let resp = resp.map_err(|e| format_sdk_error(&e, &secret_id))?;
+    Ok(format!(
+        "Started rotation for secret: {} (version {})",
+        resp.name().unwrap_or("unknown"),
+        resp.version_id().unwrap_or("unknown")
+    ))
+}
+
+/// Turn off automatic rotation. An in-progress rotation is left for the Lambda to finish.
+#[tauri::command]
+pub async fn cancel_rotation(
+    profile: Option<String>,
+    secret_id: String,
+    confirmation_token: Option<String>,
+) -> Result<String, CommandError> {
+    guard::check_write(
+        profile.as_deref(),
+        ProtectedAction::Rotate,
+        &secret_id,
+        confirmation_token.as_deref(),
+    )?;
+    let client = aws_helper::secrets_client(profile.clone()).await;
+    let resp = client
+        .cancel_rotate_secret()
+        .secret_id(&secret_id)
+        .send()
+        .await;
+    audit::record(
+        &client,
+        profile.as_deref(),
+        AuditAction::CancelRotation,
+        &secret_id,
+        AuditOutcome::from_result(&resp, |r| (r.arn(), r.version_id())),
+    )
+    .await;
+    let resp = resp.map_err(|e| format_sdk_error(&e, &secret_id))?;
+    Ok(format!(
+        "Cancelled rotation for secret: {}",
+        resp.name().unwrap_or("unknown")
+    ))
+}
@@ -0,0 +1,101 @@
+use crate::commands::bundle;
+use crate::commands::team_policy;
+use crate::helper::aws_helper;
+use crate::helper::team_policy::PolicyViolation;
+use crate::helper::value_schema::SchemaSet;
+use serde::Serialize;
+use tauri::Emitter;
+
+// ==== Value schema validation of existing secrets ====
+/// A secret whose value fails its schemas, or could not be read.
+#[derive(Serialize, Clone)]
+pub struct SchemaFailure {
+    pub secret_id: String,
+    pub violations: Vec<PolicyViolation>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct SchemaValidationReport {
+    /// Secrets with at least one matching schema
+    pub checked: usize,
+    pub valid: usize,
+    pub failures: Vec<SchemaFailure>,
+}
+
+#[derive(Serialize, Clone)]
+struct SchemaValidationProgress {
+    secret_id: String,
+    done: usize,
+    total: usize,
+}
+
+/// Check every secret under `prefix` that a value schema or team policy schema applies to.
+/// Binary secrets and secrets without a schema are not counted.
+pub async fn validate_under_prefix(
+    profile: Option<String>,
+    prefix: &str,
+    mut on_progress: impl FnMut(&str, usize, usize),
+) -> Result<SchemaValidationReport, String> {
+    let schemas = SchemaSet::compile(&team_policy::load_policy()?.schemas)?;
+    let ids: Vec<String> =
+        bundle::select_secret_ids(profile.clone(), Some(prefix.to_string()), None)
+            .await?
+            .into_iter()
+            .filter(|name| schemas.covers(name))
+            .collect();
+
+    let client = aws_helper::secrets_client(profile).await;
+    let total = ids.len();
+    let mut report = SchemaValidationReport {
+        checked: 0,
+        valid: 0,
+        failures: Vec::new(),
+    };
+    for (i, secret_id) in ids.iter().enumerate() {
+        let failure = |violations, error| SchemaFailure {
+            secret_id: secret_id.clone(),
+            violations,
+            error,
+        };
+        match client.get_secret_value().secret_id(secret_id).send().await {
+            Err(e) => report.failures.push(failure(
+                Vec::new(),
+                Some(aws_helper::format_sdk_error(&e, secret_id)),
+            )),
+            Ok(resp) => {
+                if let Some(text) = resp.secret_string() {
+                    report.checked += 1;
+                    let violations = schemas.violations(secret_id, text);
+                    if violations.is_empty() {
+                        report.valid += 1;
+                    } else {
+                        report.failures.push(failure(violations, None));
+                    }
+                }
+            }
+        }
+        on_progress(secret_id, i + 1, total);
+    }
+    Ok(report)
+}
+
+/// Report the secrets under `prefix` whose current value does not match their schemas.
+#[tauri::command]
+pub async fn validate_secrets_under_prefix(
+    app: tauri::AppHandle,
+    profile: Option<String>,
+    prefix: String,
+) -> Result<SchemaValidationReport, String> {
+    validate_under_prefix(profile, &prefix, |secret_id, done, total| {
+        let _ = app.emit(
+            "schema_validation_progress",
+            SchemaValidationProgress {
+                secret_id: secret_id.to_string(),
+                done,
+                total,
+            },
+        );
+    })
+    .await
+}
@@ -0,0 +1,157 @@
+use crate::commands::aws::SecretWrite;
+use crate::commands::config::{self, SecretTag};
+use crate::commands::error::CommandError;
+use crate::commands::kms;
+use crate::helper::audit::{self, AuditAction};
+use crate::helper::aws_helper;
+use crate::helper::team_policy::{PolicyViolation, TeamPolicy, WriteCandidate};
+use aws_sdk_secretsmanager::operation::describe_secret::DescribeSecretOutput;
+
+// ==== Team policy (naming and content rules checked before writes) ====
+/// The policy file (empty when none is set) with the value schemas from the app settings
+/// added to its schemas, so every check goes through one policy.
+pub fn load_policy() -> Result<TeamPolicy, String> {
+    let mut policy = match config::policy_file_path() {
+        Some(path) => TeamPolicy::load(&path)?,
+        None => TeamPolicy::default(),
+    };
+    policy.schemas.extend(config::load_value_schemas());
+    Ok(policy)
+}
+
+fn violations_error(secret_id: &str, violations: Vec<PolicyViolation>) -> CommandError {
+    let first = violations
+        .first()
+        .map(|v| v.message.clone())
+        .unwrap_or_default();
+    CommandError::PolicyViolation {
+        message: if violations.len() == 1 {
+            format!("Policy rejected '{secret_id}': {first}")
+        } else {
+            format!(
+                "Policy rejected '{secret_id}' with {} violations, first: {first}",
+                violations.len()
+            )
+        },
+        secret_id: secret_id.to_string(),
+        violations,
+    }
+}
+
+/// Check a write against the policy and the value schemas. For updates, `existing`
+/// supplies the description, tags and KMS key the write leaves unchanged.
+pub async fn check_write_policy(
+    profile: Option<&str>,
+    w: &SecretWrite,
+    existing: Option<&DescribeSecretOutput>,
+) -> Result<(), CommandError> {
+    let action = if existing.is_some() {
+        AuditAction::Update
+    } else {
+        AuditAction::Create
+    };
+    check_write_with(&load_policy()?, profile, action, w, existing).await
+}
+
+/// Run the policy check, comparing KMS keys by key ARN.
+async fn check_candidate(
+    policy: &TeamPolicy,
+    profile: Option<&str>,
+    candidate: &WriteCandidate<'_>,
+) -> Result<Vec<PolicyViolation>, String> {
+    let keys = policy.kms_keys_to_resolve(profile, candidate);
+    let key_arns = if keys.is_empty() {
+        Default::default()
+    } else {
+        kms::resolve_key_arns(profile, &keys).await
+    };
+    policy.check(profile, candidate, &key_arns)
+}
+
+/// Rejections are audited as `action`.
+async fn check_write_with(
+    policy: &TeamPolicy,
+    profile: Option<&str>,
+    action: AuditAction,
+    w: &SecretWrite,
+    existing: Option<&DescribeSecretOutput>,
+) -> Result<(), CommandError> {
+    let mut tag_keys: Vec<&str> = w.tags.iter().map(|t| t.key.as_str()).collect();
+    if let Some(meta) = existing {
+        tag_keys.extend(meta.tags().iter().filter_map(|t| t.key()));
+    }
+    let candidate = WriteCandidate {
+        name: &w.secret_id,
+        value: (!w.is_binary).then_some(w.secret_value.as_str()),
+        description: w
+            .description
+            .as_deref()
+            .or_else(|| existing.and_then(|m| m.description())),
+        tag_keys,
+        kms_key_id: w
+            .kms_key_id
+            .as_deref()
+            .or_else(|| existing.and_then(|m| m.kms_key_id())),
+    };
+    let violations = check_candidate(policy, profile, &candidate).await?;
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        audit::record_rejected(profile, action, &w.secret_id, "PolicyViolation");
+        Err(violations_error(&w.secret_id, violations))
+    }
+}
+
+/// Like `check_write_policy`, fetching the existing metadata for updates when the rules need it.
+pub async fn enforce_write_policy(
+    client: &aws_sdk_secretsmanager::Client,
+    profile: Option<&str>,
+    w: &SecretWrite,
+    is_update: bool,
+) -> Result<(), CommandError> {
+    let policy = load_policy()?;
+    let existing = if is_update && policy.needs_metadata(profile) {
+        Some(
+            client
+                .describe_secret()
+                .secret_id(&w.secret_id)
+                .send()
+                .await
+                .map_err(|e| aws_helper::format_sdk_error(&e, &w.secret_id))?,
+        )
+    } else {
+        None
+    };
+    let action = if is_update {
+        AuditAction::Update
+    } else {
+        AuditAction::Create
+    };
+    check_write_with(&policy, profile, action, w, existing.as_ref()).await
+}
+
+/// Dry-run a new secret against the policy so the editor can show violations before saving.
+#[tauri::command]
+pub async fn check_secret_policy(
+    profile: Option<String>,
+    secret_id: String,
+    secret_value: String,
+    description: Option<String>,
+    tags: Option<Vec<SecretTag>>,
+    kms_key_id: Option<String>,
+    is_binary: Option<bool>,
+) -> Result<Vec<PolicyViolation>, String> {
+    let tags = tags.unwrap_or_default();
+    check_candidate(
+        &load_policy()?,
+        profile.as_deref(),
+        &WriteCandidate {
+            name: &secret_id,
+            value: (is_binary != Some(true)).then_some(secret_value.as_str()),
+            description: description.as_deref(),
+            tag_keys: tags.iter().map(|t| t.key.as_str()).collect(),
+            kms_key_id: kms_key_id.as_deref(),
+        },
+    )
+    .await
+}
@@ -0,0 +1,222 @@
+use crate::helper::aws_helper;
+use aws_sdk_secretsmanager::operation::describe_secret::DescribeSecretOutput;
+use std::collections::HashSet;
+
+// ==== Terraform / OpenTofu ====
+/// Quote a string as an HCL literal, escaping template sequences.
+fn hcl_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '$' | '%' if chars.peek() == Some(&'{') => {
+                out.push(c);
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Terraform resource label derived from the full secret name.
+pub fn resource_label(secret_name: &str) -> String {
+    let mut label: String = secret_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    while label.contains("__") {
+        label = label.replace("__", "_");
+    }
+    let label = label.trim_matches('_').to_string();
+    match label.chars().next() {
+        None => "secret".to_string(),
+        Some(c) if c.is_ascii_digit() => format!("secret_{label}"),
+        Some(_) => label,
+    }
+}
+
+fn current_version_id(resp: &DescribeSecretOutput) -> Option<&str> {
+    resp.version_ids_to_stages()?
+        .iter()
+        .find(|(_, stages)| stages.iter().any(|s| s == "AWSCURRENT"))
+        .map(|(id, _)| id.as_str())
+}
+
+fn render_secret(
+    resp: &DescribeSecretOutput,
+    label: &str,
+    region: Option<&str>,
+    include_version: bool,
+    include_import: bool,
+    binary: bool,
+) -> Result<String, String> {
+    let name = resp.name().ok_or("Secret has no name")?;
+    let arn = resp.arn().ok_or("Secret has no ARN")?;
+    let mut out = String::new();
+
+    // A replica is managed through the `replica` block of its primary
+    if let (Some(primary), Some(region)) = (resp.primary_region(), region) {
+        if primary != region {
+            out.push_str(&format!(
+                "# {name} is a replica; manage it from the primary secret in {primary}\n"
+            ));
+            return Ok(out);
+        }
+    }
+
+    out.push_str(&format!(
+        "resource \"aws_secretsmanager_secret\" \"{label}\" {{\n"
+    ));
+    out.push_str(&format!("  name = {}\n", hcl_string(name)));
+    if let Some(desc) = resp.description().filter(|d| !d.is_empty()) {
+        out.push_str(&format!("  description = {}\n", hcl_string(desc)));
+    }
+    if let Some(kms) = resp.kms_key_id() {
+        out.push_str(&format!("  kms_key_id = {}\n", hcl_string(kms)));
+    }
+    let tags = resp.tags();
+    if !tags.is_empty() {
+        out.push_str("\n  tags = {\n");
+        for tag in tags {
+            out.push_str(&format!(
+                "    {} = {}\n",
+                hcl_string(tag.key().unwrap_or_default()),
+                hcl_string(tag.value().unwrap_or_default())
+            ));
+        }
+        out.push_str("  }\n");
+    }
+    for replica in resp.replication_status() {
+        let Some(replica_region) = replica.region() else {
+            continue;
+        };
+        out.push_str("\n  replica {\n");
+        out.push_str(&format!("    region = {}\n", hcl_string(replica_region)));
+        if let Some(kms) = replica.kms_key_id() {
+            out.push_str(&format!("    kms_key_id = {}\n", hcl_string(kms)));
+        }
+        out.push_str("  }\n");
+    }
+    out.push_str("}\n");
+
+    let version_id = current_version_id(resp);
+    // Binary values go through `secret_binary`, which takes base64
+    let value_attr = if binary {
+        "secret_binary"
+    } else {
+        "secret_string"
+    };
+    if include_version {
+        if binary {
+            out.push_str(&format!(
+                "\n# {name} holds a binary value; pass it base64-encoded\n"
+            ));
+        }
+        out.push_str(&format!(
+            "\nvariable \"{label}_value\" {{\n  type      = string\n  sensitive = true\n}}\n"
+        ));
+        out.push_str(&format!(
+            "\nresource \"aws_secretsmanager_secret_version\" \"{label}\" {{\n"
+        ));
+        out.push_str(&format!(
+            "  secret_id     = aws_secretsmanager_secret.{label}.id\n"
+        ));
+        out.push_str(&format!("  {value_attr} = var.{label}_value\n"));
+        out.push_str("\n  # The value keeps being managed outside Terraform\n");
+        out.push_str(&format!(
+            "  lifecycle {{\n    ignore_changes = [{value_attr}]\n  }}\n"
+        ));
+        out.push_str("}\n");
+    }
+
+    if include_import {
+        out.push_str(&format!(
+            "\nimport {{\n  to = aws_secretsmanager_secret.{label}\n  id = {}\n}}\n",
+            hcl_string(arn)
+        ));
+        if include_version {
+            match version_id {
+                Some(v) => out.push_str(&format!(
+                    "\nimport {{\n  to = aws_secretsmanager_secret_version.{label}\n  id = {}\n}}\n",
+                    hcl_string(&format!("{arn}|{v}"))
+                )),
+                None => out.push_str(&format!(
+                    "\n# {name} has no AWSCURRENT version to import\n"
+                )),
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Emit `aws_secretsmanager_secret` resources (and optionally versions) plus
+/// `import {}` blocks so existing secrets can be adopted by Terraform/OpenTofu.
+/// Secret values are never written; versions read them from a sensitive variable
+/// (base64 through `secret_binary` for binary secrets).
+#[tauri::command]
+pub async fn generate_terraform(
+    profile: Option<String>,
+    secret_ids: Vec<String>,
+    include_version: Option<bool>,
+    include_import: Option<bool>,
+) -> Result<String, String> {
+    if secret_ids.is_empty() {
+        return Err("Select at least one secret".to_string());
+    }
+    let client = aws_helper::secrets_client(profile).await;
+    let region = client.config().region().map(|r| r.to_string());
+    let mut blocks = Vec::with_capacity(secret_ids.len());
+    let mut used_labels = HashSet::new();
+    for secret_id in &secret_ids {
+        let resp = client
+            .describe_secret()
+            .secret_id(secret_id)
+            .send()
+            .await
+            .map_err(|e| aws_helper::format_sdk_error(&e, secret_id))?;
+        // The value itself is not written, only whether it is binary
+        let binary = if include_version == Some(true) {
+            client
+                .get_secret_value()
+                .secret_id(secret_id)
+                .send()
+                .await
+                .map_err(|e| aws_helper::format_sdk_error(&e, secret_id))?
+                .secret_binary()
+                .is_some()
+        } else {
+            false
+        };
+        // Names like `a/b` and `a-b` map to the same label
+        let base = resource_label(resp.name().unwrap_or(secret_id));
+        let mut label = base.clone();
+        let mut n = 2;
+        while !used_labels.insert(label.clone()) {
+            label = format!("{base}_{n}");
+            n += 1;
+        }
+        blocks.push(render_secret(
+            &resp,
+            &label,
+            region.as_deref(),
+            include_version.unwrap_or(false),
+            include_import.unwrap_or(true),
+            binary,
+        )?);
+    }
+    Ok(blocks.join("\n"))
+}
@@ -0,0 +1,304 @@
+use crate::helper::aws_helper;
+use aws_smithy_runtime_api::client::{orchestrator::HttpResponse, result::SdkError};
+use aws_smithy_types::date_time::{DateTime, Format as DateTimeFormat};
+use aws_smithy_types::error::metadata::ProvideErrorMetadata;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+// Rotate at 5 MB and keep `audit.1.jsonl` .. `audit.4.jsonl` next to the current file
+const MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
+const KEEP_ROTATED: usize = 4;
+
+/// Serializes appends and rotation between concurrent commands; `audit.lock` does the same
+/// between processes (the app and the CLI).
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+/// Account id per profile, for failed calls that return no ARN. `None` records a lookup
+/// that failed, so it is not retried on every audited call.
+static ACCOUNT_IDS: Mutex<Option<HashMap<String, Option<String>>>> = Mutex::new(None);
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+    Restore,
+    Rotate,
+    CancelRotation,
+    PutResourcePolicy,
+    DeleteResourcePolicy,
+    AddReplica,
+    RemoveReplica,
+    PromoteReplica,
+    Tag,
+    /// Only recorded for rejected attempts; the writes themselves are audited per secret
+    Copy,
+    Move,
+    Import,
+    /// A rejected put or delete of a resource policy
+    ResourcePolicy,
+    /// A rejected replica change
+    Replication,
+}
+
+/// One mutating call. Secret values are never recorded.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AuditRecord {
+    pub timestamp: String,
+    pub profile: Option<String>,
+    pub account_id: Option<String>,
+    pub region: Option<String>,
+    pub action: AuditAction,
+    pub secret_id: String,
+    pub version_id: Option<String>,
+    pub success: bool,
+    pub error_code: Option<String>,
+}
+
+/// What the AWS call returned, reduced to the fields worth auditing.
+pub struct AuditOutcome {
+    arn: Option<String>,
+    version_id: Option<String>,
+    error_code: Option<String>,
+}
+
+impl AuditOutcome {
+    pub fn ok(arn: Option<&str>, version_id: Option<&str>) -> Self {
+        AuditOutcome {
+            arn: arn.map(|s| s.to_string()),
+            version_id: version_id.map(|s| s.to_string()),
+            error_code: None,
+        }
+    }
+
+    pub fn failed<E: ProvideErrorMetadata>(e: &SdkError<E, HttpResponse>) -> Self {
+        let code = match e {
+            SdkError::ServiceError(se) => se.err().code().unwrap_or("ServiceError"),
+            SdkError::DispatchFailure(_) => "DispatchFailure",
+            SdkError::TimeoutError(_) => "TimeoutError",
+            SdkError::ResponseError(_) => "ResponseError",
+            SdkError::ConstructionFailure(_) => "ConstructionFailure",
+            _ => "Unknown",
+        };
+        AuditOutcome {
+            arn: None,
+            version_id: None,
+            error_code: Some(code.to_string()),
+        }
+    }
+
+    /// Audit outcome for an SDK result, taking ARN and version id from the response.
+    pub fn from_result<T, E: ProvideErrorMetadata>(
+        result: &Result<T, SdkError<E, HttpResponse>>,
+        fields: impl Fn(&T) -> (Option<&str>, Option<&str>),
+    ) -> Self {
+        match result {
+            Ok(resp) => {
+                let (arn, version_id) = fields(resp);
+                Self::ok(arn, version_id)
+            }
+            Err(e) => Self::failed(e),
+        }
+    }
+}
+
+fn audit_dir() -> Option<PathBuf> {
+    let dir = dirs::config_dir()?;
+    Some(dir.join("secmanager").join("audit"))
+}
+
+fn log_path(dir: &std::path::Path, index: usize) -> PathBuf {
+    if index == 0 {
+        dir.join("audit.jsonl")
+    } else {
+        dir.join(format!("audit.{index}.jsonl"))
+    }
+}
+
+/// `arn:aws:secretsmanager:<region>:<account>:secret:<name>`
+fn split_arn(arn: &str) -> Option<(String, String)> {
+    let parts: Vec<&str> = arn.splitn(6, ':').collect();
+    (parts.len() == 6).then(|| (parts[3].to_string(), parts[4].to_string()))
+}
+
+/// Account id of the profile, looked up with STS at most once per profile and session.
+async fn account_id(profile: Option<&str>) -> Option<String> {
+    let key = profile.unwrap_or("default").to_string();
+    if let Some(cached) = ACCOUNT_IDS
+        .lock()
+        .ok()?
+        .as_ref()
+        .and_then(|m| m.get(&key).cloned())
+    {
+        return cached;
+    }
+    let config = aws_helper::load_sdk_config(profile.map(|p| p.to_string())).await;
+    let id = aws_sdk_sts::Client::new(&config)
+        .get_caller_identity()
+        .send()
+        .await
+        .ok()
+        .and_then(|r| r.account().map(|a| a.to_string()));
+    remember_account(&key, id.clone());
+    id
+}
+
+fn remember_account(profile: &str, account_id: Option<String>) {
+    if let Ok(mut guard) = ACCOUNT_IDS.lock() {
+        guard
+            .get_or_insert_with(HashMap::new)
+            .insert(profile.to_string(), account_id);
+    }
+}
+
+/// Append one record for a mutating call. Audit failures never fail the call itself.
+pub async fn record(
+    client: &aws_sdk_secretsmanager::Client,
+    profile: Option<&str>,
+    action: AuditAction,
+    secret_id: &str,
+    outcome: AuditOutcome,
+) {
+    let (region, account_id) = match outcome.arn.as_deref().and_then(split_arn) {
+        Some((region, account)) => {
+            remember_account(profile.unwrap_or("default"), Some(account.clone()));
+            (Some(region), Some(account))
+        }
+        None => (
+            client.config().region().map(|r| r.to_string()),
+            account_id(profile).await,
+        ),
+    };
+    let entry = AuditRecord {
+        timestamp: aws_helper::format_date(&DateTime::from(std::time::SystemTime::now()))
+            .unwrap_or_default(),
+        profile: profile.map(|p| p.to_string()),
+        account_id,
+        region,
+        action,
+        secret_id: secret_id.to_string(),
+        version_id: outcome.version_id,
+        success: outcome.error_code.is_none(),
+        error_code: outcome.error_code,
+    };
+    write_record(&entry);
+}
+
+/// Record a write rejected before reaching AWS (read-only profile, missing confirmation,
+/// policy violation). `reason` goes into `error_code`.
+pub fn record_rejected(profile: Option<&str>, action: AuditAction, secret_id: &str, reason: &str) {
+    let key = profile.unwrap_or("default");
+    let account_id = ACCOUNT_IDS
+        .lock()
+        .ok()
+        .and_then(|m| m.as_ref().and_then(|m| m.get(key).cloned()))
+        .flatten();
+    write_record(&AuditRecord {
+        timestamp: aws_helper::format_date(&DateTime::from(std::time::SystemTime::now()))
+            .unwrap_or_default(),
+        profile: profile.map(|p| p.to_string()),
+        account_id,
+        region: None,
+        action,
+        secret_id: secret_id.to_string(),
+        version_id: None,
+        success: false,
+        error_code: Some(reason.to_string()),
+    });
+}
+
+/// Audit failures never fail the call itself, but they are logged.
+fn write_record(entry: &AuditRecord) {
+    if let Err(e) = append(entry) {
+        log::warn!(
+            "Failed to write audit record for {:?} on '{}': {e}",
+            entry.action,
+            entry.secret_id
+        );
+    }
+}
+
+fn append(entry: &AuditRecord) -> Result<(), String> {
+    let dir = audit_dir().ok_or("No config directory")?;
+    let _guard = WRITE_LOCK.lock().map_err(|e| e.to_string())?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    // Held until the file is closed at the end of this function
+    let lock = File::create(dir.join("audit.lock")).map_err(|e| e.to_string())?;
+    lock.lock().map_err(|e| e.to_string())?;
+    let current = log_path(&dir, 0);
+    if fs::metadata(&current).is_ok_and(|m| m.len() >= MAX_FILE_BYTES) {
+        rotate(&dir);
+    }
+    let mut line = serde_json::to_string(entry).map_err(|e| e.to_string())?;
+    line.push('\n');
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&current)
+        .and_then(|mut f| f.write_all(line.as_bytes()))
+        .map_err(|e| e.to_string())
+}
+
+fn rotate(dir: &std::path::Path) {
+    let _ = fs::remove_file(log_path(dir, KEEP_ROTATED));
+    for i in (0..KEEP_ROTATED).rev() {
+        let _ = fs::rename(log_path(dir, i), log_path(dir, i + 1));
+    }
+}
+
+fn parse_time(value: &str) -> Result<DateTime, String> {
+    DateTime::from_str(value, DateTimeFormat::DateTime)
+        .map_err(|e| format!("Invalid date '{value}' (expected RFC 3339): {e}"))
+}
+
+/// Records matching the filters, newest first. `secret_id` matches exactly or as a `prefix/`.
+pub fn query(
+    secret_id: Option<&str>,
+    from: Option<&str>,
+    to: Option<&str>,
+    limit: Option<usize>,
+) -> Result<Vec<AuditRecord>, String> {
+    let from = from.map(parse_time).transpose()?;
+    let to = to.map(parse_time).transpose()?;
+    let Some(dir) = audit_dir() else {
+        return Ok(Vec::new());
+    };
+    let mut out = Vec::new();
+    for i in 0..=KEEP_ROTATED {
+        let Ok(data) = fs::read_to_string(log_path(&dir, i)) else {
+            continue;
+        };
+        let mut records: Vec<AuditRecord> = data
+            .lines()
+            .filter_map(|l| serde_json::from_str(l).ok())
+            .collect();
+        records.reverse();
+        for r in records {
+            if let Some(id) = secret_id {
+                let under_prefix = id.ends_with('/') && r.secret_id.starts_with(id);
+                if r.secret_id != id && !under_prefix {
+                    continue;
+                }
+            }
+            if from.is_some() || to.is_some() {
+                let Ok(at) = parse_time(&r.timestamp) else {
+                    continue;
+                };
+                if from.is_some_and(|f| at.secs() < f.secs())
+                    || to.is_some_and(|t| at.secs() > t.secs())
+                {
+                    continue;
+                }
+            }
+            out.push(r);
+            if limit.is_some_and(|n| out.len() >= n) {
+                return Ok(out);
+            }
+        }
+    }
+    Ok(out)
+}
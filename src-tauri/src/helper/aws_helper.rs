@@ -1,3 +1,6 @@
+use aws_smithy_runtime_api::client::{orchestrator::HttpResponse, result::SdkError};
+use aws_smithy_types::date_time::{DateTime, Format as DateTimeFormat};
+use aws_smithy_types::error::metadata::ProvideErrorMetadata;
 use std::path::PathBuf;
 
 pub fn find_aws_cli_path() -> Result<PathBuf, String> {
@@ -28,3 +31,64 @@ pub fn find_aws_cli_path() -> Result<PathBuf, String> {
     }
     Err("Not found aws cli".to_string())
 }
+
+pub async fn load_sdk_config(profile: Option<String>) -> aws_config::SdkConfig {
+    load_sdk_config_in_region(profile, None).await
+}
+
+/// Like `load_sdk_config`, but overrides the profile's region when `region` is set.
+pub async fn load_sdk_config_in_region(
+    profile: Option<String>,
+    region: Option<String>,
+) -> aws_config::SdkConfig {
+    let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest());
+    if let Some(p) = profile {
+        loader = loader.profile_name(p);
+    }
+    if let Some(r) = region {
+        loader = loader.region(aws_config::Region::new(r));
+    }
+    loader.load().await
+}
+
+pub async fn secrets_client(profile: Option<String>) -> aws_sdk_secretsmanager::Client {
+    aws_sdk_secretsmanager::Client::new(&load_sdk_config(profile).await)
+}
+
+pub async fn secrets_client_in_region(
+    profile: Option<String>,
+    region: Option<String>,
+) -> aws_sdk_secretsmanager::Client {
+    aws_sdk_secretsmanager::Client::new(&load_sdk_config_in_region(profile, region).await)
+}
+
+/// RFC 3339 string for AWS timestamps shared with the FE.
+pub fn format_date(dt: &DateTime) -> Option<String> {
+    dt.fmt(DateTimeFormat::DateTime).ok()
+}
+
+pub fn is_not_found<E: ProvideErrorMetadata>(e: &SdkError<E, HttpResponse>) -> bool {
+    matches!(e, SdkError::ServiceError(se) if se.err().code() == Some("ResourceNotFoundException"))
+}
+
+/// Generic friendly message for operations without a dedicated formatter.
+pub fn format_sdk_error<E: ProvideErrorMetadata + std::fmt::Debug>(
+    e: &SdkError<E, HttpResponse>,
+    secret_id: &str,
+) -> String {
+    match e {
+        SdkError::ServiceError(se) => {
+            let err = se.err();
+            let code = err.code().unwrap_or("");
+            let message = err.message().unwrap_or("Unknown service error");
+            match code {
+                "ResourceNotFoundException" => format!("Secret '{secret_id}' does not exist"),
+                "AccessDeniedException" => format!("Access denied for '{secret_id}': {message}"),
+                _ => format!("{code}: {message}"),
+            }
+        }
+        SdkError::DispatchFailure(df) => format!("Network/dispatch error: {df:?}"),
+        SdkError::TimeoutError(te) => format!("Request timed out: {te:?}"),
+        other => format!("SDK error: {other:?}"),
+    }
+}
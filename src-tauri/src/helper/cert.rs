@@ -0,0 +1,667 @@
+use crate::helper::{aws_helper, pem};
+use aws_smithy_types::DateTime;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use x509_parser::der_parser::{ber::BerObject, oid::Oid, parse_der};
+use x509_parser::extensions::GeneralName;
+use x509_parser::objects::{oid2sn, oid_registry};
+use x509_parser::prelude::{parse_x509_certificate, X509Certificate};
+use x509_parser::public_key::PublicKey;
+
+/// Certificates expiring within this many days get a warning.
+pub const EXPIRY_WARNING_DAYS: i64 = 30;
+
+const JKS_MAGIC: u32 = 0xFEED_FEED;
+const JCEKS_MAGIC: u32 = 0xCECE_CECE;
+
+// PKCS#7 `id-data` and PKCS#12 `keyBag` OIDs, DER contents without tag and length
+const OID_DATA: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x07, 0x01];
+const OID_KEY_BAG: &[u8] = &[
+    0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x0C, 0x0A, 0x01, 0x01,
+];
+
+// Named curves by DER-encoded OID, for SEC1 keys whose parameters are not parsed
+const CURVES: &[(&[u8], &str, usize)] = &[
+    (
+        &[0x06, 0x08, 0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x03, 0x01, 0x07],
+        "prime256v1",
+        256,
+    ),
+    (
+        &[0x06, 0x05, 0x2B, 0x81, 0x04, 0x00, 0x22],
+        "secp384r1",
+        384,
+    ),
+    (
+        &[0x06, 0x05, 0x2B, 0x81, 0x04, 0x00, 0x23],
+        "secp521r1",
+        521,
+    ),
+    (
+        &[0x06, 0x05, 0x2B, 0x81, 0x04, 0x00, 0x0A],
+        "secp256k1",
+        256,
+    ),
+];
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ContainerFormat {
+    Pem,
+    Der,
+    Pkcs12,
+    Jks,
+    Unknown,
+}
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct KeyDetails {
+    /// `RSA`, `EC`, `Ed25519`, ...
+    pub algorithm: String,
+    pub bits: Option<usize>,
+    pub curve: Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct CertificateDetails {
+    /// Keystore alias, for PKCS#12 and JKS entries
+    pub alias: Option<String>,
+    pub subject: String,
+    pub issuer: String,
+    pub serial: String,
+    pub subject_alt_names: Vec<String>,
+    pub not_before: Option<String>,
+    pub not_after: Option<String>,
+    pub not_after_epoch: i64,
+    pub days_remaining: i64,
+    pub expired: bool,
+    pub expiring_soon: bool,
+    pub is_ca: bool,
+    pub self_signed: bool,
+    pub signature_algorithm: String,
+    pub public_key: KeyDetails,
+    /// SHA-256 of the DER encoding, colon-separated hex
+    pub fingerprint_sha256: String,
+}
+
+/// Describes a private key without exposing any of its material.
+#[derive(Serialize, Clone, Debug)]
+pub struct PrivateKeyDetails {
+    pub alias: Option<String>,
+    /// `PKCS#8`, `PKCS#1`, `SEC1`, `OpenSSH`, `PKCS#12` or `JKS`
+    pub format: String,
+    pub encrypted: bool,
+    /// `None` when the key is encrypted and no certificate reveals its type
+    pub key: Option<KeyDetails>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Inspection {
+    pub format: ContainerFormat,
+    pub certificates: Vec<CertificateDetails>,
+    pub private_keys: Vec<PrivateKeyDetails>,
+    /// Certificates that are expired or expire soon
+    pub warnings: Vec<String>,
+    /// Parts of the data that could not be read
+    pub errors: Vec<String>,
+    /// PKCS#12 that could not be opened with the given (or empty) password
+    pub password_required: bool,
+}
+
+impl Inspection {
+    fn new(format: ContainerFormat) -> Self {
+        Inspection {
+            format,
+            certificates: Vec::new(),
+            private_keys: Vec::new(),
+            warnings: Vec::new(),
+            errors: Vec::new(),
+            password_required: false,
+        }
+    }
+
+    fn add_certificate(&mut self, der: &[u8], alias: Option<&str>, now_secs: i64) {
+        match certificate_details(der, now_secs) {
+            Some(mut cert) => {
+                cert.alias = alias.map(|a| a.to_string());
+                if cert.expired {
+                    self.warnings.push(format!(
+                        "Certificate '{}' expired {} days ago",
+                        cert.subject, -cert.days_remaining
+                    ));
+                } else if cert.expiring_soon {
+                    self.warnings.push(format!(
+                        "Certificate '{}' expires in {} days",
+                        cert.subject, cert.days_remaining
+                    ));
+                }
+                self.certificates.push(cert);
+            }
+            None => self
+                .errors
+                .push("A certificate could not be parsed".to_string()),
+        }
+    }
+}
+
+pub fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// Detect and inspect PEM text, a DER certificate or key, a PKCS#12 bundle or a JKS keystore.
+/// `password` is only used for PKCS#12; an empty password is tried when none is given.
+pub fn inspect(bytes: &[u8], password: Option<&str>, now_secs: i64) -> Inspection {
+    if let Some(text) = std::str::from_utf8(bytes)
+        .ok()
+        .filter(|t| pem::contains_pem(t))
+    {
+        return inspect_pem(text, now_secs);
+    }
+    if bytes.len() >= 4 {
+        let magic = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        if magic == JKS_MAGIC || magic == JCEKS_MAGIC {
+            return inspect_jks(bytes, now_secs);
+        }
+    }
+    if parse_x509_certificate(bytes).is_ok() {
+        let mut out = Inspection::new(ContainerFormat::Der);
+        out.add_certificate(bytes, None, now_secs);
+        return out;
+    }
+    if looks_like_pkcs12(bytes) {
+        return inspect_pkcs12(bytes, password, now_secs);
+    }
+    if let Some(key) = pkcs8_details(bytes) {
+        let mut out = Inspection::new(ContainerFormat::Der);
+        out.private_keys.push(PrivateKeyDetails {
+            alias: None,
+            format: "PKCS#8".to_string(),
+            encrypted: false,
+            key: Some(key),
+        });
+        return out;
+    }
+    Inspection::new(ContainerFormat::Unknown)
+}
+
+fn inspect_pem(text: &str, now_secs: i64) -> Inspection {
+    let mut out = Inspection::new(ContainerFormat::Pem);
+    for block in pem::blocks(text) {
+        if block.is_certificate() {
+            out.add_certificate(&block.der, None, now_secs);
+        } else if block.is_private_key() {
+            out.private_keys.push(pem_key_details(&block));
+        }
+    }
+    out
+}
+
+fn pem_key_details(block: &pem::PemBlock) -> PrivateKeyDetails {
+    let encrypted = !block.is_unencrypted_private_key();
+    let (format, key) = match block.label.as_str() {
+        "PRIVATE KEY" => ("PKCS#8", pkcs8_details(&block.der)),
+        "ENCRYPTED PRIVATE KEY" => ("PKCS#8", None),
+        "RSA PRIVATE KEY" => (
+            "PKCS#1",
+            Some(KeyDetails {
+                algorithm: "RSA".to_string(),
+                bits: (!encrypted).then(|| rsa_bits(&block.der)).flatten(),
+                curve: None,
+            }),
+        ),
+        "EC PRIVATE KEY" => (
+            "SEC1",
+            Some(if encrypted {
+                KeyDetails {
+                    algorithm: "EC".to_string(),
+                    ..Default::default()
+                }
+            } else {
+                sec1_details(&block.der)
+            }),
+        ),
+        "DSA PRIVATE KEY" => (
+            "DSA",
+            Some(KeyDetails {
+                algorithm: "DSA".to_string(),
+                ..Default::default()
+            }),
+        ),
+        "OPENSSH PRIVATE KEY" => ("OpenSSH", openssh_details(&block.der)),
+        _ => ("PEM", None),
+    };
+    PrivateKeyDetails {
+        alias: None,
+        format: format.to_string(),
+        encrypted,
+        key,
+    }
+}
+
+fn certificate_details(der: &[u8], now_secs: i64) -> Option<CertificateDetails> {
+    let (_, cert) = parse_x509_certificate(der).ok()?;
+    let validity = cert.validity();
+    let not_after = validity.not_after.timestamp();
+    let days_remaining = (not_after - now_secs).div_euclid(86_400);
+    let fingerprint = Sha256::digest(der)
+        .iter()
+        .map(|b| format!("{b:02X}"))
+        .collect::<Vec<_>>()
+        .join(":");
+    Some(CertificateDetails {
+        alias: None,
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        serial: cert.raw_serial_as_string(),
+        subject_alt_names: subject_alt_names(&cert),
+        not_before: format_epoch(validity.not_before.timestamp()),
+        not_after: format_epoch(not_after),
+        not_after_epoch: not_after,
+        days_remaining,
+        expired: not_after < now_secs,
+        expiring_soon: not_after >= now_secs && days_remaining < EXPIRY_WARNING_DAYS,
+        is_ca: cert
+            .basic_constraints()
+            .ok()
+            .flatten()
+            .is_some_and(|b| b.value.ca),
+        self_signed: cert.subject() == cert.issuer(),
+        signature_algorithm: oid_name(&cert.signature_algorithm.algorithm),
+        public_key: public_key_details(&cert),
+        fingerprint_sha256: fingerprint,
+    })
+}
+
+fn format_epoch(secs: i64) -> Option<String> {
+    aws_helper::format_date(&DateTime::from_secs(secs))
+}
+
+fn oid_name(oid: &Oid) -> String {
+    oid2sn(oid, oid_registry())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|_| oid.to_id_string())
+}
+
+fn subject_alt_names(cert: &X509Certificate) -> Vec<String> {
+    let Ok(Some(san)) = cert.subject_alternative_name() else {
+        return Vec::new();
+    };
+    san.value
+        .general_names
+        .iter()
+        .filter_map(|name| match name {
+            GeneralName::DNSName(s) => Some(format!("DNS:{s}")),
+            GeneralName::RFC822Name(s) => Some(format!("email:{s}")),
+            GeneralName::URI(s) => Some(format!("URI:{s}")),
+            GeneralName::IPAddress(bytes) => match bytes.len() {
+                4 => Some(format!(
+                    "IP:{}",
+                    std::net::Ipv4Addr::from(<[u8; 4]>::try_from(*bytes).ok()?)
+                )),
+                16 => Some(format!(
+                    "IP:{}",
+                    std::net::Ipv6Addr::from(<[u8; 16]>::try_from(*bytes).ok()?)
+                )),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+fn public_key_details(cert: &X509Certificate) -> KeyDetails {
+    let spki = cert.public_key();
+    let curve = spki
+        .algorithm
+        .parameters
+        .as_ref()
+        .and_then(|p| p.as_oid().ok())
+        .map(|oid| oid_name(&oid));
+    match spki.parsed() {
+        Ok(PublicKey::RSA(rsa)) => KeyDetails {
+            algorithm: "RSA".to_string(),
+            bits: Some(rsa.key_size()),
+            curve: None,
+        },
+        Ok(PublicKey::EC(point)) => KeyDetails {
+            algorithm: "EC".to_string(),
+            bits: Some(point.key_size()),
+            curve,
+        },
+        Ok(key) => KeyDetails {
+            algorithm: oid_name(&spki.algorithm.algorithm),
+            bits: Some(key.key_size()).filter(|b| *b > 0),
+            curve: None,
+        },
+        Err(_) => KeyDetails {
+            algorithm: oid_name(&spki.algorithm.algorithm),
+            ..Default::default()
+        },
+    }
+}
+
+/// Bit length of a big-endian unsigned integer.
+fn integer_bits(bytes: &[u8]) -> Option<usize> {
+    let bytes = match bytes.iter().position(|b| *b != 0) {
+        Some(i) => &bytes[i..],
+        None => return None,
+    };
+    Some((bytes.len() - 1) * 8 + (8 - bytes[0].leading_zeros() as usize))
+}
+
+fn sequence<'a>(obj: &'a BerObject) -> Option<&'a Vec<BerObject<'a>>> {
+    obj.as_sequence().ok()
+}
+
+/// Modulus size of a PKCS#1 `RSAPrivateKey`.
+fn rsa_bits(der: &[u8]) -> Option<usize> {
+    let (_, obj) = parse_der(der).ok()?;
+    integer_bits(sequence(&obj)?.get(1)?.as_slice().ok()?)
+}
+
+fn sec1_details(der: &[u8]) -> KeyDetails {
+    let curve = CURVES
+        .iter()
+        .find(|(oid, _, _)| der.windows(oid.len()).any(|w| w == *oid));
+    KeyDetails {
+        algorithm: "EC".to_string(),
+        bits: curve.map(|(_, _, bits)| *bits),
+        curve: curve.map(|(_, name, _)| name.to_string()),
+    }
+}
+
+/// Algorithm and size of an unencrypted PKCS#8 `PrivateKeyInfo`.
+fn pkcs8_details(der: &[u8]) -> Option<KeyDetails> {
+    let (_, obj) = parse_der(der).ok()?;
+    let seq = sequence(&obj)?;
+    let alg = sequence(seq.get(1)?)?;
+    let oid = alg.first()?.as_oid().ok()?;
+    let key = seq.get(2)?.as_slice().ok()?;
+    let name = oid_name(oid);
+    Some(match name.as_str() {
+        "rsaEncryption" => KeyDetails {
+            algorithm: "RSA".to_string(),
+            bits: rsa_bits(key),
+            curve: None,
+        },
+        "id-ecPublicKey" => {
+            let curve = alg.get(1).and_then(|p| p.as_oid().ok()).map(oid_name);
+            let bits = CURVES
+                .iter()
+                .find(|(_, n, _)| Some(*n) == curve.as_deref())
+                .map(|(_, _, bits)| *bits);
+            KeyDetails {
+                algorithm: "EC".to_string(),
+                bits,
+                curve,
+            }
+        }
+        "ed25519" | "id-Ed25519" => KeyDetails {
+            algorithm: "Ed25519".to_string(),
+            bits: Some(256),
+            curve: None,
+        },
+        _ => KeyDetails {
+            algorithm: name,
+            ..Default::default()
+        },
+    })
+}
+
+/// Read one SSH wire-format string.
+fn ssh_string(data: &[u8]) -> Option<(&[u8], &[u8])> {
+    let len = u32::from_be_bytes(data.get(..4)?.try_into().ok()?) as usize;
+    Some((data.get(4..4 + len)?, data.get(4 + len..)?))
+}
+
+/// Key type from the public part of an `openssh-key-v1` blob, which is never encrypted.
+fn openssh_details(der: &[u8]) -> Option<KeyDetails> {
+    let rest = der.strip_prefix(b"openssh-key-v1\0")?;
+    let (_cipher, rest) = ssh_string(rest)?;
+    let (_kdf, rest) = ssh_string(rest)?;
+    let (_kdf_options, rest) = ssh_string(rest)?;
+    let (public, _) = ssh_string(rest.get(4..)?)?;
+    let (key_type, fields) = ssh_string(public)?;
+    let key_type = std::str::from_utf8(key_type).ok()?;
+    Some(match key_type {
+        "ssh-rsa" => {
+            let (_e, fields) = ssh_string(fields)?;
+            let (n, _) = ssh_string(fields)?;
+            KeyDetails {
+                algorithm: "RSA".to_string(),
+                bits: integer_bits(n),
+                curve: None,
+            }
+        }
+        "ssh-ed25519" => KeyDetails {
+            algorithm: "Ed25519".to_string(),
+            bits: Some(256),
+            curve: None,
+        },
+        t if t.starts_with("ecdsa-sha2-") => {
+            let curve = t.trim_start_matches("ecdsa-sha2-").to_string();
+            KeyDetails {
+                algorithm: "EC".to_string(),
+                bits: curve.trim_start_matches("nistp").parse().ok(),
+                curve: Some(curve),
+            }
+        }
+        t => KeyDetails {
+            algorithm: t.to_string(),
+            ..Default::default()
+        },
+    })
+}
+
+/// `PFX ::= SEQUENCE { version INTEGER (3), authSafe ContentInfo, ... }`
+fn looks_like_pkcs12(bytes: &[u8]) -> bool {
+    let Ok((_, obj)) = parse_der(bytes) else {
+        return false;
+    };
+    sequence(&obj).is_some_and(|seq| {
+        seq.first().and_then(|v| v.as_u32().ok()) == Some(3)
+            && seq.get(1).and_then(sequence).is_some()
+    })
+}
+
+/// One DER element: tag byte, contents and the bytes that follow it.
+fn der_element(data: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let tag = *data.first()?;
+    let first = *data.get(1)? as usize;
+    let (len, start) = if first < 0x80 {
+        (first, 2)
+    } else {
+        let n = first & 0x7F;
+        if n == 0 || n > 4 {
+            return None;
+        }
+        let len = data
+            .get(2..2 + n)?
+            .iter()
+            .fold(0usize, |acc, b| (acc << 8) | *b as usize);
+        (len, 2 + n)
+    };
+    let end = start.checked_add(len)?;
+    Some((tag, data.get(start..end)?, data.get(end..)?))
+}
+
+/// The octet string inside an `id-data` ContentInfo; `None` for other (encrypted) content.
+fn pkcs7_data(info: &[u8]) -> Option<&[u8]> {
+    let (tag, oid, rest) = der_element(info)?;
+    if tag != 0x06 || oid != OID_DATA {
+        return None;
+    }
+    let (_, explicit, _) = der_element(rest)?;
+    let (_, octets, _) = der_element(explicit)?;
+    Some(octets)
+}
+
+/// PKCS#8 keys stored in a plain `keyBag` outside encrypted content. Keys in a
+/// `pkcs8ShroudedKeyBag` or inside `encryptedData` are encrypted, even with an empty password.
+fn pkcs12_plain_keys(bytes: &[u8]) -> Option<Vec<&[u8]>> {
+    let mut keys = Vec::new();
+    let (_, pfx, _) = der_element(bytes)?;
+    let (_, _version, rest) = der_element(pfx)?;
+    let (_, auth_safe, _) = der_element(rest)?;
+    let (_, mut infos, _) = der_element(pkcs7_data(auth_safe)?)?;
+    while !infos.is_empty() {
+        let (_, info, rest) = der_element(infos)?;
+        infos = rest;
+        let Some(contents) = pkcs7_data(info) else {
+            continue;
+        };
+        let (_, mut bags, _) = der_element(contents)?;
+        while !bags.is_empty() {
+            let (_, bag, rest) = der_element(bags)?;
+            bags = rest;
+            let (tag, bag_id, rest) = der_element(bag)?;
+            if tag == 0x06 && bag_id == OID_KEY_BAG {
+                let (_, key, _) = der_element(rest)?;
+                keys.push(key);
+            }
+        }
+    }
+    Some(keys)
+}
+
+fn inspect_pkcs12(bytes: &[u8], password: Option<&str>, now_secs: i64) -> Inspection {
+    let mut out = Inspection::new(ContainerFormat::Pkcs12);
+    let store = match p12_keystore::KeyStore::from_pkcs12(bytes, password.unwrap_or("")) {
+        Ok(store) => store,
+        Err(_) => {
+            out.password_required = true;
+            out.errors.push(if password.is_some() {
+                "Wrong password for the PKCS#12 bundle".to_string()
+            } else {
+                "The PKCS#12 bundle needs a password".to_string()
+            });
+            return out;
+        }
+    };
+    // A layout this walker cannot follow (e.g. BER indefinite lengths) counts as encrypted
+    let mut plain_keys = pkcs12_plain_keys(bytes).unwrap_or_default();
+    for (alias, entry) in store.entries() {
+        match entry {
+            p12_keystore::KeyStoreEntry::PrivateKeyChain(chain) => {
+                let plain = plain_keys.iter().position(|k| *k == chain.key());
+                out.private_keys.push(PrivateKeyDetails {
+                    alias: Some(alias.clone()),
+                    format: "PKCS#12".to_string(),
+                    encrypted: plain.map(|i| plain_keys.remove(i)).is_none(),
+                    key: pkcs8_details(chain.key()),
+                });
+                for cert in chain.chain() {
+                    out.add_certificate(cert.as_der(), Some(alias), now_secs);
+                }
+            }
+            p12_keystore::KeyStoreEntry::Certificate(cert) => {
+                out.add_certificate(cert.as_der(), Some(alias), now_secs)
+            }
+            p12_keystore::KeyStoreEntry::Secret(_) => {}
+        }
+    }
+    // The keystore reader skips plain key bags; report them from the structure
+    for key in plain_keys {
+        out.private_keys.push(PrivateKeyDetails {
+            alias: None,
+            format: "PKCS#12".to_string(),
+            encrypted: false,
+            key: pkcs8_details(key),
+        });
+    }
+    out
+}
+
+/// Sequential reader over a JKS/JCEKS stream.
+struct JksReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> JksReader<'a> {
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        let (head, rest) = (self.data.get(..n)?, self.data.get(n..)?);
+        self.data = rest;
+        Some(head)
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        Some(u16::from_be_bytes(self.take(2)?.try_into().ok()?))
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_be_bytes(self.take(4)?.try_into().ok()?))
+    }
+
+    fn utf(&mut self) -> Option<String> {
+        let len = self.u16()? as usize;
+        Some(String::from_utf8_lossy(self.take(len)?).into_owned())
+    }
+
+    /// Version 2 stores the certificate type before each certificate.
+    fn certificate(&mut self, version: u32) -> Option<&'a [u8]> {
+        if version == 2 {
+            self.utf()?;
+        }
+        let len = self.u32()? as usize;
+        self.take(len)
+    }
+}
+
+/// Certificates are stored in the clear; key entries stay encrypted and are never decoded.
+fn inspect_jks(bytes: &[u8], now_secs: i64) -> Inspection {
+    let mut out = Inspection::new(ContainerFormat::Jks);
+    let mut r = JksReader { data: bytes };
+    let parsed = (|| {
+        r.u32()?;
+        let version = r.u32()?;
+        let count = r.u32()?;
+        for _ in 0..count {
+            let tag = r.u32()?;
+            let alias = r.utf()?;
+            r.take(8)?;
+            match tag {
+                1 => {
+                    let key_len = r.u32()? as usize;
+                    r.take(key_len)?;
+                    let chain_len = r.u32()?;
+                    let mut chain = Vec::new();
+                    for _ in 0..chain_len {
+                        chain.push(r.certificate(version)?);
+                    }
+                    let key = chain.first().and_then(|der| {
+                        let (_, cert) = parse_x509_certificate(der).ok()?;
+                        Some(public_key_details(&cert))
+                    });
+                    out.private_keys.push(PrivateKeyDetails {
+                        alias: Some(alias.clone()),
+                        format: "JKS".to_string(),
+                        encrypted: true,
+                        key,
+                    });
+                    for der in chain {
+                        out.add_certificate(der, Some(&alias), now_secs);
+                    }
+                }
+                2 => {
+                    let der = r.certificate(version)?;
+                    out.add_certificate(der, Some(&alias), now_secs);
+                }
+                _ => {
+                    out.errors.push(format!(
+                        "Entry '{alias}' is a JCEKS secret key; remaining entries were not read"
+                    ));
+                    return Some(());
+                }
+            }
+        }
+        Some(())
+    })();
+    if parsed.is_none() {
+        out.errors
+            .push("The keystore is truncated or malformed".to_string());
+    }
+    out
+}
@@ -0,0 +1,151 @@
+use serde_json::{Map, Value};
+
+fn is_valid_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Values made only of these characters are written unquoted.
+fn is_bare(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-./:@%+,".contains(c))
+}
+
+fn quote(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '$' => out.push_str("\\$"),
+            '`' => out.push_str("\\`"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            _ => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Render a flat JSON object as `.env` lines.
+/// Numbers and booleans are written as-is, nested objects/arrays as compact JSON strings.
+pub fn to_dotenv(root: &Value) -> Result<String, String> {
+    let map = root
+        .as_object()
+        .ok_or_else(|| "Only JSON objects can be converted to .env".to_string())?;
+    let invalid: Vec<&str> = map
+        .keys()
+        .filter(|k| !is_valid_key(k))
+        .map(|k| k.as_str())
+        .collect();
+    if !invalid.is_empty() {
+        return Err(format!(
+            "Keys are not valid environment variable names: {}",
+            invalid.join(", ")
+        ));
+    }
+    let mut out = String::new();
+    for (key, value) in map {
+        let raw = match value {
+            Value::String(s) => s.clone(),
+            Value::Null => String::new(),
+            other => other.to_string(),
+        };
+        let rendered = if is_bare(&raw) { raw } else { quote(&raw) };
+        out.push_str(&format!("{key}={rendered}\n"));
+    }
+    Ok(out)
+}
+
+/// Parse `.env` content into a JSON object of string values.
+/// Supports comments, `export ` prefixes, single quotes (literal) and
+/// double quotes (escapes, may span lines). Later duplicates win.
+pub fn parse_dotenv(content: &str) -> Result<Map<String, Value>, String> {
+    let mut out = Map::new();
+    let mut lines = content.lines().enumerate();
+    while let Some((idx, line)) = lines.next() {
+        let line_no = idx + 1;
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let trimmed = trimmed.strip_prefix("export ").unwrap_or(trimmed);
+        let (key, rest) = trimmed
+            .split_once('=')
+            .ok_or_else(|| format!("Line {line_no}: expected KEY=VALUE"))?;
+        let key = key.trim();
+        if !is_valid_key(key) {
+            return Err(format!("Line {line_no}: invalid variable name '{key}'"));
+        }
+        let rest = rest.trim_start();
+
+        let value = if let Some(body) = rest.strip_prefix('\'') {
+            let end = body
+                .find('\'')
+                .ok_or_else(|| format!("Line {line_no}: unterminated single quote"))?;
+            body[..end].to_string()
+        } else if let Some(body) = rest.strip_prefix('"') {
+            let mut raw = body.to_string();
+            // Keep reading lines until the closing quote is found
+            let end = loop {
+                if let Some(end) = find_closing_quote(&raw) {
+                    break end;
+                }
+                match lines.next() {
+                    Some((_, next)) => {
+                        raw.push('\n');
+                        raw.push_str(next);
+                    }
+                    None => return Err(format!("Line {line_no}: unterminated double quote")),
+                }
+            };
+            unescape(&raw[..end])
+        } else {
+            // Unquoted: an inline comment starts at ` #`
+            let value = match rest.find(" #") {
+                Some(pos) => &rest[..pos],
+                None => rest,
+            };
+            value.trim_end().to_string()
+        };
+        out.insert(key.to_string(), Value::String(value));
+    }
+    Ok(out)
+}
+
+fn find_closing_quote(s: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return Some(i),
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
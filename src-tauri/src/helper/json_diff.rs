@@ -0,0 +1,69 @@
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct JsonChange {
+    /// Dot path of the key; empty for the root value
+    pub path: String,
+    pub kind: ChangeKind,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// Key-level differences between two JSON values.
+/// Objects are compared recursively; arrays and scalars are compared as a whole.
+pub fn diff(before: &Value, after: &Value) -> Vec<JsonChange> {
+    let mut out = Vec::new();
+    diff_at("", before, after, &mut out);
+    out
+}
+
+fn diff_at(path: &str, before: &Value, after: &Value, out: &mut Vec<JsonChange>) {
+    let child = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{path}.{key}")
+        }
+    };
+    match (before, after) {
+        (Value::Object(a), Value::Object(b)) => {
+            for (key, old) in a {
+                match b.get(key) {
+                    Some(new) => diff_at(&child(key), old, new, out),
+                    None => out.push(JsonChange {
+                        path: child(key),
+                        kind: ChangeKind::Removed,
+                        before: Some(old.clone()),
+                        after: None,
+                    }),
+                }
+            }
+            for (key, new) in b {
+                if !a.contains_key(key) {
+                    out.push(JsonChange {
+                        path: child(key),
+                        kind: ChangeKind::Added,
+                        before: None,
+                        after: Some(new.clone()),
+                    });
+                }
+            }
+        }
+        _ if before != after => out.push(JsonChange {
+            path: path.to_string(),
+            kind: ChangeKind::Changed,
+            before: Some(before.clone()),
+            after: Some(after.clone()),
+        }),
+        _ => {}
+    }
+}
@@ -0,0 +1,91 @@
+use serde_json::Value;
+
+/// Split a key path into segments.
+/// Accepts a JSON pointer (`/db/password`, with `~0`/`~1` escapes) or a dot path (`db.password`).
+/// Array elements are addressed by index in both forms (`/hosts/0`, `hosts.0`).
+pub fn parse_path(path: &str) -> Result<Vec<String>, String> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Err("Key path is empty".to_string());
+    }
+    let segments: Vec<String> = if let Some(pointer) = path.strip_prefix('/') {
+        pointer
+            .split('/')
+            .map(|s| s.replace("~1", "/").replace("~0", "~"))
+            .collect()
+    } else {
+        path.split('.').map(|s| s.to_string()).collect()
+    };
+    if segments.iter().any(|s| s.is_empty()) {
+        return Err(format!("Invalid key path '{path}': empty segment"));
+    }
+    Ok(segments)
+}
+
+pub fn get<'a>(root: &'a Value, segments: &[String]) -> Option<&'a Value> {
+    let mut current = root;
+    for seg in segments {
+        current = match current {
+            Value::Object(map) => map.get(seg)?,
+            Value::Array(items) => items.get(seg.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+    Some(current)
+}
+
+/// Set the value at `segments`, creating intermediate objects when they are missing.
+/// Existing arrays can be indexed or appended to (index == len), but are never created implicitly.
+pub fn set(root: &mut Value, segments: &[String], value: Value) -> Result<(), String> {
+    let Some((last, parents)) = segments.split_last() else {
+        return Err("Key path is empty".to_string());
+    };
+    let mut current = root;
+    for (i, seg) in parents.iter().enumerate() {
+        let at = segments[..=i].join(".");
+        current = match current {
+            Value::Object(map) => map
+                .entry(seg.clone())
+                .or_insert_with(|| Value::Object(Default::default())),
+            Value::Array(items) => {
+                let idx = parse_index(seg, &at)?;
+                items
+                    .get_mut(idx)
+                    .ok_or_else(|| format!("Index out of range at '{at}'"))?
+            }
+            _ => return Err(not_a_container(&segments[..i])),
+        };
+    }
+    match current {
+        Value::Object(map) => {
+            map.insert(last.clone(), value);
+            Ok(())
+        }
+        Value::Array(items) => {
+            let at = segments.join(".");
+            let idx = parse_index(last, &at)?;
+            if idx < items.len() {
+                items[idx] = value;
+            } else if idx == items.len() {
+                items.push(value);
+            } else {
+                return Err(format!("Index out of range at '{at}'"));
+            }
+            Ok(())
+        }
+        _ => Err(not_a_container(parents)),
+    }
+}
+
+fn not_a_container(path: &[String]) -> String {
+    if path.is_empty() {
+        "Secret value is not a JSON object".to_string()
+    } else {
+        format!("'{}' is not an object or array", path.join("."))
+    }
+}
+
+fn parse_index(seg: &str, at: &str) -> Result<usize, String> {
+    seg.parse::<usize>()
+        .map_err(|_| format!("Expected an array index at '{at}'"))
+}
@@ -0,0 +1,261 @@
+use crate::helper::pem;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use x509_parser::prelude::parse_x509_certificate;
+
+/// Below this many estimated bits a credential is reported as weak.
+const MIN_ENTROPY_BITS: f64 = 40.0;
+/// Shorter values are too common to report as reused.
+const MIN_DUPLICATE_LEN: usize = 8;
+
+/// Values that are placeholders whatever the key.
+const PLACEHOLDERS: &[&str] = &[
+    "changeme",
+    "change_me",
+    "change-me",
+    "changeit",
+    "todo",
+    "tbd",
+    "fixme",
+    "placeholder",
+    "replaceme",
+    "replace_me",
+    "dummy",
+];
+
+/// Values that are only placeholders for credential keys; `mode: test` or `proxy: none` are real.
+const CREDENTIAL_PLACEHOLDERS: &[&str] = &[
+    "example",
+    "password",
+    "secret",
+    "test",
+    "null",
+    "none",
+    "undefined",
+];
+
+/// Key names whose values are treated as credentials.
+const SENSITIVE_KEY_PARTS: &[&str] = &[
+    "pass",
+    "pwd",
+    "secret",
+    "token",
+    "apikey",
+    "api_key",
+    "api-key",
+    "credential",
+    "private",
+];
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum LintRule {
+    EmptyValue,
+    Placeholder,
+    WeakPassword,
+    UnencryptedPrivateKey,
+    ExpiredCertificate,
+    DuplicateValue,
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// One problem in one secret. Messages never contain the value itself.
+#[derive(Serialize, Clone, Debug)]
+pub struct LintFinding {
+    pub secret_id: String,
+    /// JSON pointer to the key; empty for the whole value
+    pub path: String,
+    pub rule: LintRule,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// A string leaf of a secret: `(JSON pointer, key name, value)`.
+pub struct Leaf {
+    pub path: String,
+    pub key: Option<String>,
+    pub value: String,
+}
+
+impl Leaf {
+    /// Credential-like: a plain (non-JSON) value, or a key named like a password or token.
+    pub fn is_sensitive(&self) -> bool {
+        match &self.key {
+            None => !pem::contains_pem(&self.value),
+            Some(key) => {
+                let key = key.to_ascii_lowercase();
+                SENSITIVE_KEY_PARTS.iter().any(|p| key.contains(p))
+            }
+        }
+    }
+}
+
+/// The string leaves of a JSON object or array, or the whole text otherwise.
+pub fn leaves(text: &str) -> Vec<Leaf> {
+    match serde_json::from_str::<Value>(text) {
+        Ok(v @ (Value::Object(_) | Value::Array(_))) => {
+            let mut out = Vec::new();
+            collect(&v, String::new(), None, &mut out);
+            out
+        }
+        _ => vec![Leaf {
+            path: String::new(),
+            key: None,
+            value: text.to_string(),
+        }],
+    }
+}
+
+fn collect(v: &Value, path: String, key: Option<&str>, out: &mut Vec<Leaf>) {
+    match v {
+        Value::Object(map) => {
+            for (k, child) in map {
+                let escaped = k.replace('~', "~0").replace('/', "~1");
+                collect(child, format!("{path}/{escaped}"), Some(k), out);
+            }
+        }
+        Value::Array(items) => {
+            for (i, child) in items.iter().enumerate() {
+                collect(child, format!("{path}/{i}"), key, out);
+            }
+        }
+        Value::String(s) => out.push(Leaf {
+            path,
+            key: key.map(|k| k.to_string()),
+            value: s.clone(),
+        }),
+        _ => {}
+    }
+}
+
+/// A placeholder for a credential key, which also counts common words like `test` or `password`.
+pub fn is_placeholder(value: &str) -> bool {
+    let v = value.trim().to_ascii_lowercase();
+    CREDENTIAL_PLACEHOLDERS.contains(&v.as_str()) || is_generic_placeholder(value)
+}
+
+/// A placeholder for any key: `changeme`, `TODO`, `xxx`, `<...>`, `${...}`, `your-...`.
+pub fn is_generic_placeholder(value: &str) -> bool {
+    let v = value.trim().to_ascii_lowercase();
+    PLACEHOLDERS.contains(&v.as_str())
+        || (v.len() >= 3 && v.chars().all(|c| c == 'x' || c == '*'))
+        || (v.starts_with('<') && v.ends_with('>'))
+        || (v.starts_with("${") && v.ends_with('}'))
+        || v.starts_with("your-")
+        || v.starts_with("your_")
+}
+
+/// Shannon entropy of the characters times the length, in bits.
+pub fn entropy_bits(value: &str) -> f64 {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for c in value.chars() {
+        *counts.entry(c).or_default() += 1;
+    }
+    let len = value.chars().count() as f64;
+    let per_char: f64 = counts
+        .values()
+        .map(|&n| {
+            let p = n as f64 / len;
+            -p * p.log2()
+        })
+        .sum();
+    per_char * len
+}
+
+/// Findings for one leaf, except duplicates which need every secret.
+pub fn lint_leaf(leaf: &Leaf, now_secs: i64) -> Vec<(LintRule, Severity, String)> {
+    let mut out = Vec::new();
+    if leaf.value.trim().is_empty() {
+        out.push((
+            LintRule::EmptyValue,
+            Severity::Warning,
+            "Value is empty".to_string(),
+        ));
+        return out;
+    }
+    // A placeholder credential is an error; under other keys it may be a real setting
+    let sensitive = leaf.is_sensitive();
+    if sensitive && is_placeholder(&leaf.value) {
+        out.push((
+            LintRule::Placeholder,
+            Severity::Error,
+            "Value looks like a placeholder".to_string(),
+        ));
+        return out;
+    }
+    if !sensitive && is_generic_placeholder(&leaf.value) {
+        out.push((
+            LintRule::Placeholder,
+            Severity::Warning,
+            "Value looks like a placeholder".to_string(),
+        ));
+        return out;
+    }
+    if pem::contains_pem(&leaf.value) {
+        out.extend(lint_pem(&leaf.value, now_secs));
+        return out;
+    }
+    if sensitive {
+        let bits = entropy_bits(&leaf.value);
+        if bits < MIN_ENTROPY_BITS {
+            out.push((
+                LintRule::WeakPassword,
+                Severity::Warning,
+                format!(
+                    "Estimated entropy is {bits:.0} bits (minimum {MIN_ENTROPY_BITS:.0}); {} characters",
+                    leaf.value.chars().count()
+                ),
+            ));
+        }
+    }
+    out
+}
+
+/// Unencrypted private keys and expired certificates in PEM text.
+pub fn lint_pem(text: &str, now_secs: i64) -> Vec<(LintRule, Severity, String)> {
+    let mut out = Vec::new();
+    for block in pem::blocks(text) {
+        if block.is_unencrypted_private_key() {
+            out.push((
+                LintRule::UnencryptedPrivateKey,
+                Severity::Error,
+                format!("{} is not protected by a passphrase", block.label),
+            ));
+        } else if block.is_certificate() {
+            out.extend(lint_certificate_der(&block.der, now_secs));
+        }
+    }
+    out
+}
+
+/// An expired X.509 certificate in DER form; anything unparsable yields nothing.
+pub fn lint_certificate_der(der: &[u8], now_secs: i64) -> Option<(LintRule, Severity, String)> {
+    let (_, cert) = parse_x509_certificate(der).ok()?;
+    let not_after = cert.validity().not_after;
+    (not_after.timestamp() < now_secs).then(|| {
+        (
+            LintRule::ExpiredCertificate,
+            Severity::Error,
+            format!("Certificate '{}' expired on {not_after}", cert.subject()),
+        )
+    })
+}
+
+/// Fingerprint used to spot reuse without keeping values around.
+pub fn value_digest(value: &str) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(value.as_bytes()).into()
+}
+
+pub fn is_duplicate_candidate(leaf: &Leaf) -> bool {
+    leaf.is_sensitive()
+        && leaf.value.chars().count() >= MIN_DUPLICATE_LEN
+        && !is_placeholder(&leaf.value)
+}
@@ -1 +1,12 @@
+pub mod audit;
 pub mod aws_helper;
+pub mod cert;
+pub mod dotenv;
+pub mod json_diff;
+pub mod json_path;
+pub mod lint;
+pub mod password;
+pub mod pem;
+pub mod team_policy;
+pub mod text_format;
+pub mod value_schema;
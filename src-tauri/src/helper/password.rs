@@ -0,0 +1,88 @@
+use rand::rngs::OsRng;
+use rand::seq::SliceRandom;
+use serde::Deserialize;
+
+// Same character classes and limits as Secrets Manager GetRandomPassword
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const NUMBERS: &str = "0123456789";
+const PUNCTUATION: &str = "!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+const DEFAULT_LENGTH: i64 = 32;
+const MAX_LENGTH: i64 = 4096;
+
+/// Options mirroring `GetRandomPassword`, shared by the server-side and local generators.
+#[derive(Deserialize, Clone, Default, Debug)]
+pub struct PasswordOptions {
+    pub length: Option<i64>,
+    pub exclude_characters: Option<String>,
+    pub exclude_numbers: Option<bool>,
+    pub exclude_punctuation: Option<bool>,
+    pub exclude_uppercase: Option<bool>,
+    pub exclude_lowercase: Option<bool>,
+    pub include_space: Option<bool>,
+    pub require_each_included_type: Option<bool>,
+}
+
+impl PasswordOptions {
+    pub fn length(&self) -> i64 {
+        self.length.unwrap_or(DEFAULT_LENGTH)
+    }
+}
+
+/// Generate a password locally from the OS CSPRNG, without calling AWS.
+pub fn generate_local(opts: &PasswordOptions) -> Result<String, String> {
+    let length = opts.length();
+    if !(1..=MAX_LENGTH).contains(&length) {
+        return Err(format!(
+            "Password length must be between 1 and {MAX_LENGTH}"
+        ));
+    }
+    let excluded: Vec<char> = opts
+        .exclude_characters
+        .as_deref()
+        .unwrap_or("")
+        .chars()
+        .collect();
+
+    let mut classes: Vec<Vec<char>> = Vec::new();
+    let mut add_class = |set: &str, skip: Option<bool>| {
+        if skip == Some(true) {
+            return;
+        }
+        let chars: Vec<char> = set.chars().filter(|c| !excluded.contains(c)).collect();
+        if !chars.is_empty() {
+            classes.push(chars);
+        }
+    };
+    add_class(LOWERCASE, opts.exclude_lowercase);
+    add_class(UPPERCASE, opts.exclude_uppercase);
+    add_class(NUMBERS, opts.exclude_numbers);
+    add_class(PUNCTUATION, opts.exclude_punctuation);
+    add_class(" ", Some(opts.include_space != Some(true)));
+
+    if classes.is_empty() {
+        return Err("No characters left to generate a password from".to_string());
+    }
+    let require_each = opts.require_each_included_type.unwrap_or(true);
+    let length = length as usize;
+    if require_each && length < classes.len() {
+        return Err(format!(
+            "Password length must be at least {} to include every character type",
+            classes.len()
+        ));
+    }
+
+    let mut rng = OsRng;
+    let pool: Vec<char> = classes.iter().flatten().copied().collect();
+    let mut out: Vec<char> = Vec::with_capacity(length);
+    if require_each {
+        for class in &classes {
+            out.extend(class.choose(&mut rng));
+        }
+    }
+    while out.len() < length {
+        out.extend(pool.choose(&mut rng));
+    }
+    out.shuffle(&mut rng);
+    Ok(out.into_iter().collect())
+}
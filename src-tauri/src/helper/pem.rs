@@ -0,0 +1,81 @@
+use base64::Engine as _;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// One `-----BEGIN <label>-----` block, with its body decoded.
+pub struct PemBlock {
+    pub label: String,
+    /// Legacy OpenSSL encryption (`Proc-Type: 4,ENCRYPTED` header)
+    pub proc_encrypted: bool,
+    pub der: Vec<u8>,
+}
+
+fn block_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?s)-----BEGIN ([A-Z0-9 ]+)-----(.*?)-----END ([A-Z0-9 ]+)-----").unwrap()
+    })
+}
+
+/// Every well-formed PEM block in `text`. Blocks whose body is not base64 are skipped.
+pub fn blocks(text: &str) -> Vec<PemBlock> {
+    let mut out = Vec::new();
+    for caps in block_regex().captures_iter(text) {
+        if caps[1] != caps[3] {
+            continue;
+        }
+        let mut proc_encrypted = false;
+        let mut body = String::new();
+        for line in caps[2].lines().map(str::trim) {
+            if let Some((name, value)) = line.split_once(':') {
+                proc_encrypted |= name == "Proc-Type" && value.contains("ENCRYPTED");
+            } else {
+                body.push_str(line);
+            }
+        }
+        if let Ok(der) = base64::engine::general_purpose::STANDARD.decode(&body) {
+            out.push(PemBlock {
+                label: caps[1].to_string(),
+                proc_encrypted,
+                der,
+            });
+        }
+    }
+    out
+}
+
+pub fn contains_pem(text: &str) -> bool {
+    text.contains("-----BEGIN ")
+}
+
+impl PemBlock {
+    pub fn is_certificate(&self) -> bool {
+        matches!(
+            self.label.as_str(),
+            "CERTIFICATE" | "X509 CERTIFICATE" | "TRUSTED CERTIFICATE"
+        )
+    }
+
+    pub fn is_private_key(&self) -> bool {
+        self.label.ends_with("PRIVATE KEY")
+    }
+
+    /// Whether the key material is stored without a passphrase.
+    pub fn is_unencrypted_private_key(&self) -> bool {
+        if !self.is_private_key() || self.proc_encrypted {
+            return false;
+        }
+        match self.label.as_str() {
+            "ENCRYPTED PRIVATE KEY" => false,
+            "OPENSSH PRIVATE KEY" => openssh_cipher(&self.der).is_some_and(|c| c == "none"),
+            _ => true,
+        }
+    }
+}
+
+/// Cipher name of an `openssh-key-v1` blob.
+fn openssh_cipher(der: &[u8]) -> Option<&str> {
+    let rest = der.strip_prefix(b"openssh-key-v1\0")?;
+    let len = u32::from_be_bytes(rest.get(..4)?.try_into().ok()?) as usize;
+    std::str::from_utf8(rest.get(4..4 + len)?).ok()
+}
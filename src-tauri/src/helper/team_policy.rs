@@ -0,0 +1,196 @@
+use crate::helper::value_schema::SchemaSet;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// The key under `profiles` that applies to profiles without their own entry.
+const ANY_PROFILE: &str = "*";
+/// What `kms_key_id: None` means in Secrets Manager.
+const DEFAULT_KMS_KEY: &str = "alias/aws/secretsmanager";
+
+/// Team-managed naming and content rules, loaded from a JSON or YAML file.
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(default)]
+pub struct TeamPolicy {
+    /// Rules per profile name; `*` is the fallback for other profiles
+    pub profiles: HashMap<String, ProfileRules>,
+    /// JSON Schemas for secret values, by name prefix or glob
+    pub schemas: Vec<SchemaRule>,
+}
+
+#[derive(Deserialize, Clone, Default, Debug)]
+#[serde(default)]
+pub struct ProfileRules {
+    /// The name must match at least one of these regexes
+    pub name_patterns: Vec<String>,
+    pub required_tags: Vec<String>,
+    pub require_description: bool,
+    /// Key ids, ARNs, aliases or alias ARNs; `alias/aws/secretsmanager` allows the default key
+    pub allowed_kms_keys: Vec<String>,
+}
+
+/// A JSON Schema for the values of the secrets selected by `glob`, or else by `prefix`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SchemaRule {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    /// `*` matches within one path segment, `**` across segments
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub glob: Option<String>,
+    pub schema: Value,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct PolicyViolation {
+    /// `name_pattern`, `required_tag`, `description`, `kms_key` or `schema`
+    pub rule: String,
+    /// JSON pointer into the value, for schema violations
+    pub path: Option<String>,
+    pub message: String,
+}
+
+impl PolicyViolation {
+    fn new(rule: &str, message: String) -> Self {
+        PolicyViolation {
+            rule: rule.to_string(),
+            path: None,
+            message,
+        }
+    }
+}
+
+/// The parts of a create/update that the policy looks at.
+/// For updates, fields the caller does not change are filled from the existing secret.
+pub struct WriteCandidate<'a> {
+    pub name: &'a str,
+    /// `None` for binary secrets, which are never schema-checked
+    pub value: Option<&'a str>,
+    pub description: Option<&'a str>,
+    pub tag_keys: Vec<&'a str>,
+    pub kms_key_id: Option<&'a str>,
+}
+
+impl TeamPolicy {
+    pub fn load(path: &Path) -> Result<TeamPolicy, String> {
+        let data = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read policy file {}: {e}", path.display()))?;
+        let is_yaml = path
+            .extension()
+            .is_some_and(|ext| ext == "yaml" || ext == "yml");
+        if is_yaml {
+            serde_norway::from_str(&data).map_err(|e| format!("Invalid policy file: {e}"))
+        } else {
+            serde_json::from_str(&data).map_err(|e| format!("Invalid policy file: {e}"))
+        }
+    }
+
+    pub fn rules_for(&self, profile: Option<&str>) -> Option<&ProfileRules> {
+        self.profiles
+            .get(profile.unwrap_or("default"))
+            .or_else(|| self.profiles.get(ANY_PROFILE))
+    }
+
+    /// Only the rules whose inputs come from the existing secret on update.
+    pub fn needs_metadata(&self, profile: Option<&str>) -> bool {
+        self.rules_for(profile).is_some_and(|r| {
+            !r.required_tags.is_empty() || r.require_description || !r.allowed_kms_keys.is_empty()
+        })
+    }
+
+    /// The KMS key references `check` compares, so the caller can resolve them to key ARNs.
+    pub fn kms_keys_to_resolve<'a>(
+        &'a self,
+        profile: Option<&str>,
+        c: &WriteCandidate<'a>,
+    ) -> Vec<&'a str> {
+        match self.rules_for(profile) {
+            Some(rules) if !rules.allowed_kms_keys.is_empty() => {
+                let mut keys: Vec<&str> =
+                    rules.allowed_kms_keys.iter().map(String::as_str).collect();
+                keys.push(c.kms_key_id.unwrap_or(DEFAULT_KMS_KEY));
+                keys
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Every violation of the candidate. `key_arns` maps KMS key references to their key
+    /// ARN; unresolved references are compared as written. `Err` means the policy itself is broken.
+    pub fn check(
+        &self,
+        profile: Option<&str>,
+        c: &WriteCandidate,
+        key_arns: &HashMap<String, String>,
+    ) -> Result<Vec<PolicyViolation>, String> {
+        let mut out = Vec::new();
+        if let Some(rules) = self.rules_for(profile) {
+            check_rules(rules, c, key_arns, &mut out)?;
+        }
+        if let Some(text) = c.value {
+            out.extend(SchemaSet::compile(&self.schemas)?.violations(c.name, text));
+        }
+        Ok(out)
+    }
+}
+
+fn check_rules(
+    rules: &ProfileRules,
+    c: &WriteCandidate,
+    key_arns: &HashMap<String, String>,
+    out: &mut Vec<PolicyViolation>,
+) -> Result<(), String> {
+    if !rules.name_patterns.is_empty() {
+        let mut matched = false;
+        for pattern in &rules.name_patterns {
+            let re = Regex::new(pattern)
+                .map_err(|e| format!("Invalid name pattern '{pattern}' in policy: {e}"))?;
+            matched |= re.is_match(c.name);
+        }
+        if !matched {
+            out.push(PolicyViolation::new(
+                "name_pattern",
+                format!(
+                    "Name '{}' does not match any allowed pattern: {}",
+                    c.name,
+                    rules.name_patterns.join(", ")
+                ),
+            ));
+        }
+    }
+    for tag in &rules.required_tags {
+        if !c.tag_keys.contains(&tag.as_str()) {
+            out.push(PolicyViolation::new(
+                "required_tag",
+                format!("Tag '{tag}' is required"),
+            ));
+        }
+    }
+    if rules.require_description && c.description.is_none_or(|d| d.trim().is_empty()) {
+        out.push(PolicyViolation::new(
+            "description",
+            "A description is required".to_string(),
+        ));
+    }
+    if !rules.allowed_kms_keys.is_empty() {
+        let key = c.kms_key_id.unwrap_or(DEFAULT_KMS_KEY);
+        let arn = |k: &str| key_arns.get(k).cloned().unwrap_or_else(|| k.to_string());
+        let key_arn = arn(key);
+        let same = |allowed: &str| {
+            arn(allowed) == key_arn
+                || allowed.strip_prefix("alias/") == Some(key)
+                || key.strip_prefix("alias/") == Some(allowed)
+        };
+        if !rules.allowed_kms_keys.iter().any(|a| same(a)) {
+            out.push(PolicyViolation::new(
+                "kms_key",
+                format!(
+                    "KMS key '{key}' is not allowed; use one of: {}",
+                    rules.allowed_kms_keys.join(", ")
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
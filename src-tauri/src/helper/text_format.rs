@@ -0,0 +1,196 @@
+use serde::Deserialize;
+use serde_json::{Map, Number, Value};
+
+/// Alternative text views of a JSON secret.
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum TextFormat {
+    Yaml,
+    Toml,
+}
+
+impl TextFormat {
+    pub fn name(self) -> &'static str {
+        match self {
+            TextFormat::Yaml => "YAML",
+            TextFormat::Toml => "TOML",
+        }
+    }
+}
+
+fn child(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+fn display_path(path: &str) -> &str {
+    if path.is_empty() {
+        "<root>"
+    } else {
+        path
+    }
+}
+
+/// Render JSON in the given format.
+pub fn from_json(root: &Value, format: TextFormat) -> Result<String, String> {
+    match format {
+        TextFormat::Yaml => {
+            serde_norway::to_string(root).map_err(|e| format!("Failed to render YAML: {e}"))
+        }
+        TextFormat::Toml => {
+            let toml::Value::Table(table) = json_to_toml(root, "")? else {
+                return Err("Only JSON objects can be shown as TOML".to_string());
+            };
+            toml::to_string_pretty(&table).map_err(|e| format!("Failed to render TOML: {e}"))
+        }
+    }
+}
+
+/// Parse content in the given format back to JSON, rejecting constructs JSON cannot hold.
+/// YAML merge keys (`<<: *anchor`) are rejected too: the parser would keep `<<` as a plain
+/// key instead of merging, so the saved JSON would not mean what the YAML says.
+pub fn to_json(content: &str, format: TextFormat) -> Result<Value, String> {
+    match format {
+        TextFormat::Yaml => {
+            let value: serde_norway::Value =
+                serde_norway::from_str(content).map_err(|e| format!("Invalid YAML: {e}"))?;
+            yaml_to_json(&value, "")
+        }
+        TextFormat::Toml => {
+            let table: toml::Table =
+                toml::from_str(content).map_err(|e| format!("Invalid TOML: {e}"))?;
+            toml_to_json(&toml::Value::Table(table), "")
+        }
+    }
+}
+
+fn yaml_to_json(value: &serde_norway::Value, path: &str) -> Result<Value, String> {
+    use serde_norway::Value as Y;
+    Ok(match value {
+        Y::Null => Value::Null,
+        Y::Bool(b) => Value::Bool(*b),
+        Y::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Value::from(i)
+            } else if let Some(u) = n.as_u64() {
+                Value::from(u)
+            } else {
+                let f = n.as_f64().unwrap_or(f64::NAN);
+                Value::Number(Number::from_f64(f).ok_or_else(|| {
+                    format!(
+                        "{}: NaN/infinity cannot be stored in JSON",
+                        display_path(path)
+                    )
+                })?)
+            }
+        }
+        Y::String(s) => Value::String(s.clone()),
+        Y::Sequence(items) => Value::Array(
+            items
+                .iter()
+                .enumerate()
+                .map(|(i, v)| yaml_to_json(v, &child(path, &i.to_string())))
+                .collect::<Result<_, _>>()?,
+        ),
+        Y::Mapping(map) => {
+            let mut out = Map::new();
+            for (k, v) in map {
+                let Y::String(key) = k else {
+                    return Err(format!(
+                        "{}: non-string key {} is not allowed in JSON",
+                        display_path(path),
+                        serde_norway::to_string(k).unwrap_or_default().trim()
+                    ));
+                };
+                if key == "<<" {
+                    return Err(format!(
+                        "{}: YAML merge keys (<<) are not supported; write the merged keys out",
+                        display_path(path)
+                    ));
+                }
+                out.insert(key.clone(), yaml_to_json(v, &child(path, key))?);
+            }
+            Value::Object(out)
+        }
+        Y::Tagged(tagged) => {
+            return Err(format!(
+                "{}: YAML tag {} has no JSON equivalent",
+                display_path(path),
+                tagged.tag
+            ))
+        }
+    })
+}
+
+fn toml_to_json(value: &toml::Value, path: &str) -> Result<Value, String> {
+    use toml::Value as T;
+    Ok(match value {
+        T::String(s) => Value::String(s.clone()),
+        T::Integer(i) => Value::from(*i),
+        T::Float(f) => Value::Number(Number::from_f64(*f).ok_or_else(|| {
+            format!(
+                "{}: NaN/infinity cannot be stored in JSON",
+                display_path(path)
+            )
+        })?),
+        T::Boolean(b) => Value::Bool(*b),
+        T::Datetime(dt) => {
+            return Err(format!(
+                "{}: TOML datetime {dt} does not round-trip through JSON; quote it as a string",
+                display_path(path)
+            ))
+        }
+        T::Array(items) => Value::Array(
+            items
+                .iter()
+                .enumerate()
+                .map(|(i, v)| toml_to_json(v, &child(path, &i.to_string())))
+                .collect::<Result<_, _>>()?,
+        ),
+        T::Table(table) => Value::Object(
+            table
+                .iter()
+                .map(|(k, v)| Ok((k.clone(), toml_to_json(v, &child(path, k))?)))
+                .collect::<Result<_, String>>()?,
+        ),
+    })
+}
+
+fn json_to_toml(value: &Value, path: &str) -> Result<toml::Value, String> {
+    use toml::Value as T;
+    Ok(match value {
+        Value::Null => {
+            return Err(format!(
+                "{}: null has no TOML equivalent",
+                display_path(path)
+            ))
+        }
+        Value::Bool(b) => T::Boolean(*b),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => T::Integer(i),
+            None if n.is_u64() => {
+                return Err(format!(
+                    "{}: {n} is too large for a TOML integer",
+                    display_path(path)
+                ))
+            }
+            None => T::Float(n.as_f64().unwrap_or_default()),
+        },
+        Value::String(s) => T::String(s.clone()),
+        Value::Array(items) => T::Array(
+            items
+                .iter()
+                .enumerate()
+                .map(|(i, v)| json_to_toml(v, &child(path, &i.to_string())))
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Object(map) => T::Table(
+            map.iter()
+                .map(|(k, v)| Ok((k.clone(), json_to_toml(v, &child(path, k))?)))
+                .collect::<Result<_, String>>()?,
+        ),
+    })
+}
@@ -0,0 +1,113 @@
+use crate::helper::team_policy::{PolicyViolation, SchemaRule};
+use jsonschema::error::ValidationErrorKind;
+use jsonschema::ValidationError;
+use regex::Regex;
+use serde_json::Value;
+
+/// Translate a name glob into an anchored regex.
+/// `*` and `?` stay within one `/` segment; `**` spans segments.
+pub fn glob_regex(glob: &str) -> Result<Regex, String> {
+    if glob.trim().is_empty() {
+        return Err("Schema glob must not be empty".to_string());
+    }
+    let mut pattern = String::from("^");
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                // `**/` also matches zero segments
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    pattern.push_str("(?:.*/)?");
+                } else {
+                    pattern.push_str(".*");
+                }
+            }
+            '*' => pattern.push_str("[^/]*"),
+            '?' => pattern.push_str("[^/]"),
+            _ => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    pattern.push('$');
+    Regex::new(&pattern).map_err(|e| format!("Invalid glob '{glob}': {e}"))
+}
+
+/// The error text with the offending value masked: it may be (part of) the secret.
+/// Only the schema's own limits, patterns and property names remain.
+fn redacted_message(e: &ValidationError) -> String {
+    match &e.kind {
+        // The nested error would print the property name unmasked
+        ValidationErrorKind::PropertyNames { error } => {
+            error.masked_with("property name").to_string()
+        }
+        _ => e.masked_with("value").to_string(),
+    }
+}
+
+struct CompiledRule {
+    scope: String,
+    names: Regex,
+    validator: jsonschema::Validator,
+}
+
+/// Schema rules compiled once, then applied to any number of values.
+pub struct SchemaSet {
+    rules: Vec<CompiledRule>,
+}
+
+impl SchemaSet {
+    /// Fails on the first rule without a prefix or glob, or with an invalid glob or schema.
+    pub fn compile(rules: &[SchemaRule]) -> Result<SchemaSet, String> {
+        let mut compiled = Vec::with_capacity(rules.len());
+        for rule in rules {
+            let (scope, names) = match (&rule.glob, &rule.prefix) {
+                (Some(glob), _) => (glob.clone(), glob_regex(glob)?),
+                (None, Some(prefix)) => (
+                    prefix.clone(),
+                    Regex::new(&format!("^{}", regex::escape(prefix)))
+                        .map_err(|e| format!("Invalid prefix '{prefix}': {e}"))?,
+                ),
+                (None, None) => return Err("Schema rule needs a prefix or a glob".to_string()),
+            };
+            let validator = jsonschema::validator_for(&rule.schema)
+                .map_err(|e| format!("Invalid JSON Schema for '{scope}': {e}"))?;
+            compiled.push(CompiledRule {
+                scope,
+                names,
+                validator,
+            });
+        }
+        Ok(SchemaSet { rules: compiled })
+    }
+
+    pub fn covers(&self, name: &str) -> bool {
+        self.rules.iter().any(|r| r.names.is_match(name))
+    }
+
+    /// Violations of every rule matching `name`. A value matching several rules must satisfy all.
+    pub fn violations(&self, name: &str, text: &str) -> Vec<PolicyViolation> {
+        let mut out = Vec::new();
+        let mut value = None;
+        for rule in self.rules.iter().filter(|r| r.names.is_match(name)) {
+            let parsed = value.get_or_insert_with(|| serde_json::from_str::<Value>(text).ok());
+            let Some(parsed) = parsed else {
+                out.push(PolicyViolation {
+                    rule: "schema".to_string(),
+                    path: None,
+                    message: format!(
+                        "Value must be JSON to match the schema for '{}'",
+                        rule.scope
+                    ),
+                });
+                continue;
+            };
+            out.extend(rule.validator.iter_errors(parsed).map(|e| PolicyViolation {
+                rule: "schema".to_string(),
+                path: Some(e.instance_path.to_string()),
+                message: redacted_message(&e),
+            }));
+        }
+        out
+    }
+}
@@ -1,5 +1,5 @@
-mod commands;
-mod helper;
+pub mod commands;
+pub mod helper;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -29,14 +29,82 @@ pub fn run() {
             commands::aws::load_profiles,
             commands::aws::list_secrets,
             commands::aws::list_secrets_with_metadata,
+            commands::aws::describe_secret,
             commands::aws::fetch_secret,
             commands::aws::fetch_secret_async,
             commands::aws::create_secret,
             commands::aws::update_secret,
+            commands::aws::get_secret_json_value,
+            commands::aws::update_secret_json_value,
+            commands::aws::generate_secret_value,
+            commands::aws::create_generated_secret,
             commands::aws::delete_secret,
             commands::aws::list_deleted_secrets,
             commands::aws::restore_secret,
             commands::aws::check_sso,
+            // rotation
+            commands::rotation::get_rotation_status,
+            commands::rotation::configure_rotation,
+            commands::rotation::rotate_secret_now,
+            commands::rotation::cancel_rotation,
+            // resource policy
+            commands::policy::get_resource_policy,
+            commands::policy::put_resource_policy,
+            commands::policy::delete_resource_policy,
+            commands::policy::validate_resource_policy,
+            // kms
+            commands::kms::list_kms_aliases,
+            // replication
+            commands::replication::get_replication_status,
+            commands::replication::replicate_secret_to_regions,
+            commands::replication::remove_replica_regions,
+            commands::replication::promote_replica,
+            // bulk export / import
+            commands::bundle::export_secrets,
+            commands::bundle::plan_import,
+            commands::bundle::apply_import,
+            commands::copy::copy_secret,
+            commands::copy::copy_secrets,
+            commands::copy::move_secret,
+            commands::copy::move_folder,
+            // format conversions
+            commands::formats::secret_to_dotenv,
+            commands::formats::dotenv_to_json,
+            commands::formats::save_secret_from_dotenv,
+            commands::formats::secret_to_text_format,
+            commands::formats::text_format_to_json,
+            commands::formats::save_secret_from_text_format,
+            // kubernetes
+            commands::kubernetes::generate_k8s_secret_manifest,
+            commands::kubernetes::generate_external_secrets,
+            commands::config::load_external_secret_options,
+            commands::config::save_external_secret_options,
+            // terraform
+            commands::terraform::generate_terraform,
+            // audit log
+            commands::audit::query_audit_log,
+            // protected profiles
+            commands::config::load_profile_protection,
+            commands::config::save_profile_protection,
+            commands::guard::prepare_protected_write,
+            // read-only mode
+            commands::config::get_write_access,
+            commands::config::load_read_only_mode,
+            commands::config::save_read_only_mode,
+            // team policy
+            commands::config::load_policy_file_path,
+            commands::config::save_policy_file_path,
+            commands::team_policy::check_secret_policy,
+            // value schemas
+            commands::config::load_value_schemas,
+            commands::config::save_value_schemas,
+            commands::schema::validate_secrets_under_prefix,
+            // lint
+            commands::lint::lint_secrets,
+            // certificates
+            commands::certificates::inspect_secret_certificates,
+            commands::certificates::inspect_certificate_data,
+            commands::certificates::scan_certificate_expiry,
             // cache metadata
             commands::config::load_cached_secret_metadata,
             commands::config::save_cached_secret_metadata,
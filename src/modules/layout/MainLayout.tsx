@@ -4,6 +4,8 @@ import { useEffect, useState } from "react";
 import { useUiStore } from "../store/useUiStore";
 import { platform } from "@tauri-apps/plugin-os";
 import { ThemeToggle } from "../shared/components/ThemeToggle";
+import { ProtectedWriteModal } from "../shared/components/ProtectedWriteModal";
+import { VersionConflictModal } from "../shared/components/VersionConflictModal";
 
 export function MainLayout() {
   const [isMac, setIsMac] = useState(false);
@@ -39,6 +41,8 @@ export function MainLayout() {
       <div className="flex-1 min-h-0">
         <Outlet />
       </div>
+      <ProtectedWriteModal />
+      <VersionConflictModal />
     </div>
   );
 }
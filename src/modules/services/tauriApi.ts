@@ -1,7 +1,154 @@
 import { invoke } from "@tauri-apps/api/core";
 
-export type SecretContent = { string: string | null; binary_base64: string | null };
-export type SecretMetadata = { name: string; is_binary: boolean };
+export type SecretContent = { string: string | null; binary_base64: string | null; version_id?: string | null };
+export type SecretWriteResult = { message: string; version_id: string | null };
+export type CommandError =
+  | { kind: "failed"; message: string }
+  | { kind: "version_conflict"; message: string; secret_id: string; expected_version_id: string; current: SecretContent }
+  | { kind: "read_only"; message: string; profile: string | null }
+  | { kind: "confirmation_required"; message: string; profile: string | null; action: ProtectedAction; secret_id: string }
+  | { kind: "policy_violation"; message: string; secret_id: string; violations: PolicyViolation[] };
+export type PolicyViolation = { rule: string; path: string | null; message: string };
+export type ValueSchema = { glob: string; schema: unknown };
+export type SchemaFailure = { secret_id: string; violations: PolicyViolation[]; error: string | null };
+export type SchemaValidationReport = { checked: number; valid: number; failures: SchemaFailure[] };
+export type SchemaValidationProgress = { secret_id: string; done: number; total: number };
+export type LintRule = "empty_value" | "placeholder" | "weak_password" | "unencrypted_private_key" | "expired_certificate" | "duplicate_value";
+export type LintFinding = { secret_id: string; path: string; rule: LintRule; severity: "warning" | "error"; message: string };
+export type LintReport = { scanned: number; findings: LintFinding[]; failed: { secret_id: string; error: string }[] };
+export type LintProgress = { secret_id: string; done: number; total: number };
+export type KeyDetails = { algorithm: string; bits: number | null; curve: string | null };
+export type CertificateDetails = {
+  alias: string | null;
+  subject: string;
+  issuer: string;
+  serial: string;
+  subject_alt_names: string[];
+  not_before: string | null;
+  not_after: string | null;
+  not_after_epoch: number;
+  days_remaining: number;
+  expired: boolean;
+  expiring_soon: boolean;
+  is_ca: boolean;
+  self_signed: boolean;
+  signature_algorithm: string;
+  public_key: KeyDetails;
+  fingerprint_sha256: string;
+};
+export type PrivateKeyDetails = { alias: string | null; format: string; encrypted: boolean; key: KeyDetails | null };
+export type CertificateInspection = {
+  format: "pem" | "der" | "pkcs12" | "jks" | "unknown";
+  certificates: CertificateDetails[];
+  private_keys: PrivateKeyDetails[];
+  warnings: string[];
+  errors: string[];
+  password_required: boolean;
+};
+export type CertificateExpiry = CertificateDetails & { secret_id: string; path: string };
+export type CertificateScanReport = { scanned: number; certificates: CertificateExpiry[]; skipped: { secret_id: string; error: string }[]; failed: { secret_id: string; error: string }[] };
+export type CertificateScanProgress = { secret_id: string; done: number; total: number; found: number };
+export type ProtectedAction = "create" | "update" | "delete" | "restore" | "copy" | "move" | "import" | "rotate" | "policy" | "replicate";
+export type ProfileProtection = { protected: boolean; read_only: boolean };
+export type WriteAccess = { protected: boolean; read_only: boolean; read_only_reason: string | null; locked_by_team: boolean };
+export type SecretMetadata = {
+  name: string;
+  is_binary: boolean;
+  rotation_enabled?: boolean;
+  rotation_lambda_arn?: string | null;
+  last_rotated_date?: string | null;
+  next_rotation_date?: string | null;
+  kms_key_id?: string | null;
+  primary_region?: string | null;
+  replication_status?: ReplicaStatus[];
+};
+export type ReplicaStatus = {
+  region: string;
+  status: string | null;
+  status_message: string | null;
+  kms_key_id: string | null;
+  last_accessed_date: string | null;
+};
+export type SecretTag = { key: string; value: string };
+export type ReplicaRegion = { region: string; kms_key_id?: string | null };
+export type ReplicationInfo = {
+  secret_id: string;
+  primary_region: string | null;
+  current_region: string | null;
+  replicas: ReplicaStatus[];
+};
+export type RotationRules = {
+  automatically_after_days?: number | null;
+  duration?: string | null;
+  schedule_expression?: string | null;
+};
+export type RotationStatus = {
+  secret_id: string;
+  rotation_enabled: boolean;
+  rotation_lambda_arn: string | null;
+  rotation_rules: RotationRules | null;
+  last_rotated_date: string | null;
+  next_rotation_date: string | null;
+};
+
+export type PasswordOptions = {
+  length?: number;
+  exclude_characters?: string;
+  exclude_numbers?: boolean;
+  exclude_punctuation?: boolean;
+  exclude_uppercase?: boolean;
+  exclude_lowercase?: boolean;
+  include_space?: boolean;
+  require_each_included_type?: boolean;
+};
+export type GeneratedSecretResult = { message: string; value: string | null };
+export type PolicyFinding = { check_name: string | null; error_message: string | null };
+export type PolicyValidation = { passed: boolean; findings: PolicyFinding[] };
+export type KmsAlias = { alias_name: string; alias_arn: string | null; target_key_id: string | null; aws_managed: boolean };
+export type BundleItemError = { secret_id: string; error: string };
+export type ExportSummary = { path: string; exported: number; failed: BundleItemError[] };
+export type ImportAction = "create" | "update" | "unchanged" | "conflict";
+export type ImportPlanItem = { name: string; action: ImportAction; reason: string | null; version_id: string | null };
+export type ImportPlan = { path: string; source_profile: string | null; items: ImportPlanItem[] };
+export type ImportItemResult = { name: string; action: ImportAction; success: boolean; message: string };
+export type SecretLocation = { profile: string | null; region?: string | null };
+export type PrefixRename = { from: string; to: string };
+export type CopyResult = { source_name: string; target_name: string; action: ImportAction; success: boolean; message: string };
+export type MovedSecret = { from: string; to: string };
+export type MoveFolderResult = { moved: MovedSecret[]; rolled_back: boolean; error: string | null };
+export type TextFormat = "yaml" | "toml";
+export type AuditAction =
+  | "create" | "update" | "delete" | "restore" | "rotate" | "cancel_rotation"
+  | "put_resource_policy" | "delete_resource_policy" | "add_replica" | "remove_replica" | "promote_replica" | "tag"
+  | "copy" | "move" | "import" | "resource_policy" | "replication";
+export type AuditRecord = {
+  timestamp: string;
+  profile: string | null;
+  account_id: string | null;
+  region: string | null;
+  action: AuditAction;
+  secret_id: string;
+  version_id: string | null;
+  success: boolean;
+  error_code: string | null;
+};
+export type ExternalSecretOptions = {
+  api_version: string;
+  store_name: string;
+  store_kind: string;
+  refresh_interval: string;
+  creation_policy: string;
+  namespace: string | null;
+};
+export type ExternalSecretKeyMapping = { secret_key: string; property?: string | null };
+export type ExternalSecretItem = {
+  secret_id: string;
+  is_binary?: boolean;
+  target_name?: string | null;
+  binary_file_name?: string | null;
+  key_mappings?: ExternalSecretKeyMapping[];
+  template?: Record<string, string> | null;
+};
 
 export const api = {
   loadProfiles: () => invoke<string[]>("load_profiles"),
@@ -14,6 +161,8 @@ export const api = {
 
   listSecrets: (profile?: string | null) => invoke<string[]>("list_secrets", { profile: profile ?? null }),
   listSecretsWithMetadata: (profile?: string | null) => invoke<SecretMetadata[]>("list_secrets_with_metadata", { profile: profile ?? null }),
+  describeSecret: (profile: string | null | undefined, secretId: string) =>
+    invoke<SecretMetadata>("describe_secret", { profile: profile ?? null, secretId }),
   fetchSecret: (profile: string | null | undefined, secretId: string) =>
     invoke<SecretContent>("fetch_secret", { profile: profile ?? null, secretId }),
   fetchSecretAsync: (profile: string | null | undefined, secretId: string) =>
@@ -24,23 +173,174 @@ export const api = {
     secretValue: string,
     description?: string | null,
     isBinary?: boolean,
-  ) => invoke<string>("create_secret", { profile: profile ?? null, secretId, secretValue, description: description ?? null, isBinary: isBinary ?? false }),
+    kmsKeyId?: string | null,
+    tags?: SecretTag[] | null,
+    confirmationToken?: string | null,
+  ) => invoke<SecretWriteResult>("create_secret", { profile: profile ?? null, secretId, secretValue, description: description ?? null, isBinary: isBinary ?? false, kmsKeyId: kmsKeyId ?? null, tags: tags ?? null, confirmationToken: confirmationToken ?? null }),
   updateSecret: (
     profile: string | null | undefined,
     secretId: string,
     secretValue: string,
     description?: string | null,
     isBinary?: boolean,
-  ) => invoke<string>("update_secret", { profile: profile ?? null, secretId, secretValue, description: description ?? null, isBinary: isBinary ?? false }),
+    expectedVersionId?: string | null,
+    kmsKeyId?: string | null,
+    confirmationToken?: string | null,
+  ) => invoke<SecretWriteResult>("update_secret", { profile: profile ?? null, secretId, secretValue, description: description ?? null, isBinary: isBinary ?? false, expectedVersionId: expectedVersionId ?? null, kmsKeyId: kmsKeyId ?? null, confirmationToken: confirmationToken ?? null }),
+  getSecretJsonValue: (profile: string | null | undefined, secretId: string, path: string) =>
+    invoke<unknown>("get_secret_json_value", { profile: profile ?? null, secretId, path }),
+  updateSecretJsonValue: (profile: string | null | undefined, secretId: string, path: string, value: unknown, confirmationToken?: string | null) =>
+    invoke<string>("update_secret_json_value", { profile: profile ?? null, secretId, path, value, confirmationToken: confirmationToken ?? null }),
+  generateSecretValue: (profile: string | null | undefined, options?: PasswordOptions, local?: boolean) =>
+    invoke<string>("generate_secret_value", { profile: profile ?? null, options: options ?? null, local: local ?? false }),
+  createGeneratedSecret: (
+    profile: string | null | undefined,
+    secretId: string,
+    options?: PasswordOptions,
+    jsonKey?: string | null,
+    description?: string | null,
+    reveal?: boolean,
+    confirmationToken?: string | null,
+  ) => invoke<GeneratedSecretResult>("create_generated_secret", { profile: profile ?? null, secretId, options: options ?? null, jsonKey: jsonKey ?? null, description: description ?? null, reveal: reveal ?? false, confirmationToken: confirmationToken ?? null }),
   deleteSecret: (
     profile: string | null | undefined,
     secretId: string,
-  ) => invoke<string>("delete_secret", { profile: profile ?? null, secretId }),
+    confirmationToken?: string | null,
+  ) => invoke<string>("delete_secret", { profile: profile ?? null, secretId, confirmationToken: confirmationToken ?? null }),
   listDeletedSecrets: (profile?: string | null) => invoke<string[]>("list_deleted_secrets", { profile: profile ?? null }),
   restoreSecret: (
     profile: string | null | undefined,
     secretId: string,
-  ) => invoke<string>("restore_secret", { profile: profile ?? null, secretId }),
+    confirmationToken?: string | null,
+  ) => invoke<string>("restore_secret", { profile: profile ?? null, secretId, confirmationToken: confirmationToken ?? null }),
+  getRotationStatus: (profile: string | null | undefined, secretId: string) =>
+    invoke<RotationStatus>("get_rotation_status", { profile: profile ?? null, secretId }),
+  configureRotation: (
+    profile: string | null | undefined,
+    secretId: string,
+    rules: RotationRules,
+    lambdaArn?: string | null,
+    rotateImmediately?: boolean,
+    confirmationToken?: string | null,
+  ) => invoke<string>("configure_rotation", { profile: profile ?? null, secretId, rules, lambdaArn: lambdaArn ?? null, rotateImmediately: rotateImmediately ?? false, confirmationToken: confirmationToken ?? null }),
+  rotateSecretNow: (profile: string | null | undefined, secretId: string, confirmationToken?: string | null) =>
+    invoke<string>("rotate_secret_now", { profile: profile ?? null, secretId, confirmationToken: confirmationToken ?? null }),
+  cancelRotation: (profile: string | null | undefined, secretId: string, confirmationToken?: string | null) =>
+    invoke<string>("cancel_rotation", { profile: profile ?? null, secretId, confirmationToken: confirmationToken ?? null }),
+  getResourcePolicy: (profile: string | null | undefined, secretId: string) =>
+    invoke<string | null>("get_resource_policy", { profile: profile ?? null, secretId }),
+  putResourcePolicy: (profile: string | null | undefined, secretId: string, policy: string, blockPublicPolicy?: boolean, confirmationToken?: string | null) =>
+    invoke<string>("put_resource_policy", { profile: profile ?? null, secretId, policy, blockPublicPolicy: blockPublicPolicy ?? true, confirmationToken: confirmationToken ?? null }),
+  deleteResourcePolicy: (profile: string | null | undefined, secretId: string, confirmationToken?: string | null) =>
+    invoke<string>("delete_resource_policy", { profile: profile ?? null, secretId, confirmationToken: confirmationToken ?? null }),
+  validateResourcePolicy: (profile: string | null | undefined, secretId: string | null, policy: string) =>
+    invoke<PolicyValidation>("validate_resource_policy", { profile: profile ?? null, secretId, policy }),
+  listKmsAliases: (profile?: string | null) => invoke<KmsAlias[]>("list_kms_aliases", { profile: profile ?? null }),
+  getReplicationStatus: (profile: string | null | undefined, secretId: string) =>
+    invoke<ReplicationInfo>("get_replication_status", { profile: profile ?? null, secretId }),
+  replicateSecretToRegions: (profile: string | null | undefined, secretId: string, regions: ReplicaRegion[], forceOverwrite?: boolean, confirmationToken?: string | null) =>
+    invoke<ReplicaStatus[]>("replicate_secret_to_regions", { profile: profile ?? null, secretId, regions, forceOverwrite: forceOverwrite ?? false, confirmationToken: confirmationToken ?? null }),
+  removeReplicaRegions: (profile: string | null | undefined, secretId: string, regions: string[], confirmationToken?: string | null) =>
+    invoke<ReplicaStatus[]>("remove_replica_regions", { profile: profile ?? null, secretId, regions, confirmationToken: confirmationToken ?? null }),
+  promoteReplica: (profile: string | null | undefined, secretId: string, region: string, confirmationToken?: string | null) =>
+    invoke<string>("promote_replica", { profile: profile ?? null, secretId, region, confirmationToken: confirmationToken ?? null }),
+  exportSecrets: (
+    profile: string | null | undefined,
+    options: { prefix?: string | null; secretIds?: string[] | null; path?: string | null },
+  ) => invoke<ExportSummary | null>("export_secrets", { profile: profile ?? null, prefix: options.prefix ?? null, secretIds: options.secretIds ?? null, path: options.path ?? null }),
+  planImport: (profile: string | null | undefined, path?: string | null) =>
+    invoke<ImportPlan | null>("plan_import", { profile: profile ?? null, path: path ?? null }),
+  applyImport: (
+    profile: string | null | undefined,
+    path: string,
+    names?: string[] | null,
+    reviewedVersions?: Record<string, string | null> | null,
+    confirmationToken?: string | null,
+  ) => invoke<ImportItemResult[]>("apply_import", { profile: profile ?? null, path, names: names ?? null, reviewedVersions: reviewedVersions ?? null, confirmationToken: confirmationToken ?? null }),
+  copySecret: (
+    source: SecretLocation,
+    target: SecretLocation,
+    secretId: string,
+    options?: { rename?: PrefixRename | null; overwrite?: boolean; dryRun?: boolean },
+    confirmationToken?: string | null,
+  ) => invoke<CopyResult>("copy_secret", { source, target, secretId, rename: options?.rename ?? null, overwrite: options?.overwrite ?? false, dryRun: options?.dryRun ?? false, confirmationToken: confirmationToken ?? null }),
+  copySecrets: (
+    source: SecretLocation,
+    target: SecretLocation,
+    secretIds: string[],
+    options?: { rename?: PrefixRename | null; overwrite?: boolean; dryRun?: boolean },
+    confirmationToken?: string | null,
+  ) => invoke<CopyResult[]>("copy_secrets", { source, target, secretIds, rename: options?.rename ?? null, overwrite: options?.overwrite ?? false, dryRun: options?.dryRun ?? false, confirmationToken: confirmationToken ?? null }),
+  moveSecret: (profile: string | null | undefined, secretId: string, newName: string, recoveryWindowDays?: number | null, confirmationToken?: string | null) =>
+    invoke<string>("move_secret", { profile: profile ?? null, secretId, newName, recoveryWindowDays: recoveryWindowDays ?? null, confirmationToken: confirmationToken ?? null }),
+  moveFolder: (profile: string | null | undefined, fromPrefix: string, toPrefix: string, recoveryWindowDays?: number | null, confirmationToken?: string | null) =>
+    invoke<MoveFolderResult>("move_folder", { profile: profile ?? null, fromPrefix, toPrefix, recoveryWindowDays: recoveryWindowDays ?? null, confirmationToken: confirmationToken ?? null }),
+  secretToDotenv: (profile: string | null | undefined, secretId: string) =>
+    invoke<string>("secret_to_dotenv", { profile: profile ?? null, secretId }),
+  dotenvToJson: (content: string) => invoke<string>("dotenv_to_json", { content }),
+  saveSecretFromDotenv: (
+    profile: string | null | undefined,
+    secretId: string,
+    content: string,
+    description?: string | null,
+    expectedVersionId?: string | null,
+    confirmationToken?: string | null,
+  ) => invoke<string>("save_secret_from_dotenv", { profile: profile ?? null, secretId, content, description: description ?? null, expectedVersionId: expectedVersionId ?? null, confirmationToken: confirmationToken ?? null }),
+  secretToTextFormat: (profile: string | null | undefined, secretId: string, format: TextFormat) =>
+    invoke<string>("secret_to_text_format", { profile: profile ?? null, secretId, format }),
+  textFormatToJson: (content: string, format: TextFormat) => invoke<string>("text_format_to_json", { content, format }),
+  saveSecretFromTextFormat: (
+    profile: string | null | undefined,
+    secretId: string,
+    content: string,
+    format: TextFormat,
+    description?: string | null,
+    expectedVersionId?: string | null,
+    confirmationToken?: string | null,
+  ) => invoke<string>("save_secret_from_text_format", { profile: profile ?? null, secretId, content, format, description: description ?? null, expectedVersionId: expectedVersionId ?? null, confirmationToken: confirmationToken ?? null }),
+  generateK8sSecretManifest: (
+    profile: string | null | undefined,
+    secretId: string,
+    options?: { name?: string | null; namespace?: string | null; labels?: Record<string, string> | null; dataKey?: string | null },
+  ) => invoke<string>("generate_k8s_secret_manifest", { profile: profile ?? null, secretId, name: options?.name ?? null, namespace: options?.namespace ?? null, labels: options?.labels ?? null, dataKey: options?.dataKey ?? null }),
+  generateExternalSecrets: (profile: string | null | undefined, items: ExternalSecretItem[], options?: ExternalSecretOptions | null) =>
+    invoke<string>("generate_external_secrets", { profile: profile ?? null, items, options: options ?? null }),
+  loadExternalSecretOptions: (profile: string) => invoke<ExternalSecretOptions>("load_external_secret_options", { profile }),
+  saveExternalSecretOptions: (profile: string, options: ExternalSecretOptions) =>
+    invoke<boolean>("save_external_secret_options", { profile, options }),
+  generateTerraform: (profile: string | null | undefined, secretIds: string[], options?: { includeVersion?: boolean; includeImport?: boolean }) =>
+    invoke<string>("generate_terraform", { profile: profile ?? null, secretIds, includeVersion: options?.includeVersion ?? null, includeImport: options?.includeImport ?? null }),
+  queryAuditLog: (filter?: { secretId?: string | null; from?: string | null; to?: string | null; limit?: number | null }) =>
+    invoke<AuditRecord[]>("query_audit_log", { secretId: filter?.secretId ?? null, from: filter?.from ?? null, to: filter?.to ?? null, limit: filter?.limit ?? null }),
+  loadProfileProtection: (profile: string) => invoke<ProfileProtection>("load_profile_protection", { profile }),
+  saveProfileProtection: (profile: string, protection: ProfileProtection) =>
+    invoke<boolean>("save_profile_protection", { profile, protection }),
+  getWriteAccess: (profile: string | null | undefined) => invoke<WriteAccess>("get_write_access", { profile: profile ?? null }),
+  loadReadOnlyMode: () => invoke<boolean>("load_read_only_mode"),
+  saveReadOnlyMode: (enabled: boolean) => invoke<boolean>("save_read_only_mode", { enabled }),
+  prepareProtectedWrite: (profile: string | null | undefined, action: ProtectedAction, secretId: string, typedName: string) =>
+    invoke<string>("prepare_protected_write", { profile: profile ?? null, action, secretId, typedName }),
+  loadPolicyFilePath: () => invoke<string | null>("load_policy_file_path"),
+  savePolicyFilePath: (path: string | null) => invoke<boolean>("save_policy_file_path", { path }),
+  checkSecretPolicy: (
+    profile: string | null | undefined,
+    secretId: string,
+    secretValue: string,
+    options?: { description?: string | null; tags?: SecretTag[] | null; kmsKeyId?: string | null; isBinary?: boolean },
+  ) =>
+    invoke<PolicyViolation[]>("check_secret_policy", { profile: profile ?? null, secretId, secretValue, description: options?.description ?? null, tags: options?.tags ?? null, kmsKeyId: options?.kmsKeyId ?? null, isBinary: options?.isBinary ?? null }),
+  loadValueSchemas: () => invoke<ValueSchema[]>("load_value_schemas"),
+  saveValueSchemas: (schemas: ValueSchema[]) => invoke<boolean>("save_value_schemas", { schemas }),
+  validateSecretsUnderPrefix: (profile: string | null | undefined, prefix: string) =>
+    invoke<SchemaValidationReport>("validate_secrets_under_prefix", { profile: profile ?? null, prefix }),
+  lintSecrets: (profile: string | null | undefined, selection: { prefix?: string | null; secretIds?: string[] | null }) =>
+    invoke<LintReport>("lint_secrets", { profile: profile ?? null, prefix: selection.prefix ?? null, secretIds: selection.secretIds ?? null }),
+  inspectSecretCertificates: (profile: string | null | undefined, secretId: string, password?: string | null) =>
+    invoke<CertificateInspection>("inspect_secret_certificates", { profile: profile ?? null, secretId, password: password ?? null }),
+  inspectCertificateData: (dataBase64: string, password?: string | null) =>
+    invoke<CertificateInspection>("inspect_certificate_data", { dataBase64, password: password ?? null }),
+  scanCertificateExpiry: (profile: string | null | undefined, prefix?: string | null) =>
+    invoke<CertificateScanReport>("scan_certificate_expiry", { profile: profile ?? null, prefix: prefix ?? null }),
   checkSso: (profile: string) => invoke<boolean>("check_sso", { profile }),
   triggerSsoLogin: (profile: string) => invoke<boolean>("trigger_sso_login", { profile }),
   loadTheme: () => invoke<string | null>("load_theme"),
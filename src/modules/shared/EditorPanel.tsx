@@ -87,7 +87,8 @@ export function EditorPanel() {
       
       // Nếu checkbox được chọn và save thành công, hiển thị template
       if (shouldShowTemplate) {
-        argocdTemplate.showTemplate(
+        await argocdTemplate.showTemplate(
+          profile,
           currentSecretId,
           currentIsBinary,
           currentImportedBinary?.name
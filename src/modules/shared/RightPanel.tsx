@@ -14,6 +14,7 @@ import { useSecretsListStore } from "../store/useSecretsListStore";
 import { useEditorStore } from "../store/useEditorStore";
 import { api } from "../services/tauriApi";
 import { useLogsStore } from "../store/useLogsStore";
+import { withProtectedWrite } from "../store/useConfirmationStore";
 import { Input } from "./components/Input";
 import { Button } from "./components/Button";
 
@@ -93,7 +94,7 @@ export function RightPanel() {
       }
       try {
         pushInfo(`Restoring secret: ${secretId}`);
-        await api.restoreSecret(profile, secretId);
+        await withProtectedWrite((token) => api.restoreSecret(profile, secretId, token));
         pushSuccess(`Restored secret: ${secretId}`);
         
         // Reload deleted secrets và active secrets
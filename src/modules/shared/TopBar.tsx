@@ -6,6 +6,7 @@ import { useRef, useState } from "react";
 import { api } from "../services/tauriApi";
 import { useLogsStore } from "../store/useLogsStore";
 import { useSecretsListStore } from "../store/useSecretsListStore";
+import { withProtectedWrite } from "../store/useConfirmationStore";
 import { Modal } from "./components/Modal";
 import { Input } from "./components/Input";
 import { Button } from "./components/Button";
@@ -145,7 +146,7 @@ export function TopBar() {
 
     try {
       pushInfo(`Deleting secret: ${secretId}`);
-      await api.deleteSecret(profile, secretId);
+      await withProtectedWrite((token) => api.deleteSecret(profile, secretId, token));
       pushSuccess(`Deleted secret: ${secretId}`);
       
       // Close the tab after deletion
@@ -156,7 +157,8 @@ export function TopBar() {
       await listSecrets(profile, true);
       await listDeletedSecrets(profile);
     } catch (error) {
-      pushError(`Failed to delete secret: ${String(error)}`);
+      const errorMsg = typeof error === 'string' ? error : (error as any)?.message ?? String(error);
+      pushError(`Failed to delete secret: ${errorMsg}`);
       setShowDeleteModal(false);
     }
   };
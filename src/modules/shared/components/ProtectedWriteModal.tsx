@@ -0,0 +1,52 @@
+import { useEffect, useState } from "react";
+import { Modal } from "./Modal";
+import { Input } from "./Input";
+import { useConfirmationStore } from "../../store/useConfirmationStore";
+
+export function ProtectedWriteModal() {
+  const { pending, error, confirm, cancel } = useConfirmationStore();
+  const [typedName, setTypedName] = useState("");
+
+  useEffect(() => {
+    setTypedName("");
+  }, [pending]);
+
+  return (
+    <Modal
+      open={!!pending}
+      onClose={cancel}
+      title="Confirm protected write"
+      closeOnBackdrop={false}
+      actions={
+        <>
+          <button className="btn btn-ghost btn-sm" onClick={cancel}>
+            Cancel
+          </button>
+          <button
+            className="btn btn-error btn-sm"
+            disabled={typedName.trim() !== pending?.secretId}
+            onClick={() => confirm(typedName)}
+          >
+            Confirm {pending?.action}
+          </button>
+        </>
+      }
+    >
+      <p className="mb-2">{pending?.message}</p>
+      <p className="mb-2">
+        Type <span className="font-mono font-semibold">{pending?.secretId}</span> to continue.
+      </p>
+      <Input
+        size="sm"
+        className="w-full font-mono"
+        value={typedName}
+        autoFocus
+        onChange={(e) => setTypedName(e.target.value)}
+        onKeyDown={(e) => {
+          if (e.key === "Enter" && typedName.trim() === pending?.secretId) confirm(typedName);
+        }}
+      />
+      {error && <p className="text-error mt-2">{error}</p>}
+    </Modal>
+  );
+}
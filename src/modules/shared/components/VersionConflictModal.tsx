@@ -0,0 +1,34 @@
+import { Modal } from "./Modal";
+import { useEditorStore } from "../../store/useEditorStore";
+
+export function VersionConflictModal() {
+  const conflict = useEditorStore((s) => s.versionConflict);
+  const resolve = useEditorStore((s) => s.resolveVersionConflict);
+
+  return (
+    <Modal
+      open={!!conflict}
+      onClose={() => resolve("cancel")}
+      title="Secret changed since it was opened"
+      closeOnBackdrop={false}
+      actions={
+        <>
+          <button className="btn btn-ghost btn-sm" onClick={() => resolve("cancel")}>
+            Keep editing
+          </button>
+          <button className="btn btn-sm" onClick={() => resolve("reload")}>
+            Reload latest
+          </button>
+          <button className="btn btn-error btn-sm" onClick={() => resolve("overwrite")}>
+            Overwrite
+          </button>
+        </>
+      }
+    >
+      <p className="mb-2">
+        Someone else saved a new version of <span className="font-mono font-semibold">{conflict?.secretId}</span>.
+      </p>
+      <p>Reload the latest value and discard your edits, or overwrite it with your version.</p>
+    </Modal>
+  );
+}
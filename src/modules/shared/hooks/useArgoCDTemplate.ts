@@ -3,25 +3,29 @@ import { useLogsStore } from "../../store/useLogsStore";
 import { useEditorStore } from "../../store/useEditorStore";
 import { save } from "@tauri-apps/plugin-dialog";
 import { writeTextFile } from "@tauri-apps/plugin-fs";
-import { generateArgoCDExternalSecretTemplate } from "../utils/argocdTemplateUtils";
+import { api } from "../../services/tauriApi";
 
 export function useArgoCDTemplate() {
   const { pushSuccess, pushError } = useLogsStore();
   const [showModal, setShowModal] = useState<boolean>(false);
   const [template, setTemplate] = useState<string>("");
 
-  const showTemplate = (
+  const showTemplate = async (
+    profile: string | null | undefined,
     secretId: string,
     isBinary: boolean,
     binaryFileName?: string
   ) => {
-    const generatedTemplate = generateArgoCDExternalSecretTemplate(
-      secretId,
-      isBinary,
-      binaryFileName
-    );
-    setTemplate(generatedTemplate);
-    setShowModal(true);
+    try {
+      const generatedTemplate = await api.generateExternalSecrets(profile, [
+        { secret_id: secretId, is_binary: isBinary, binary_file_name: binaryFileName ?? null },
+      ]);
+      setTemplate(generatedTemplate);
+      setShowModal(true);
+    } catch (error) {
+      const errorMsg = typeof error === 'string' ? error : (error as any)?.message ?? String(error);
+      pushError(`Failed to generate ArgoCD template: ${errorMsg}`);
+    }
   };
 
   const exportTemplate = async () => {
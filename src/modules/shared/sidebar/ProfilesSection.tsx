@@ -6,9 +6,13 @@ import {
   Star,
   XCircle,
 } from "lucide-react";
+import { useEffect, useState } from "react";
 import { useProfileStore } from "../../store/useProfileStore";
+import { useLogsStore } from "../../store/useLogsStore";
+import { api, ProfileProtection, WriteAccess } from "../../services/tauriApi";
 import { Select } from "../components/Select";
 import { Button } from "../components/Button";
+import { Input } from "../components/Input";
 
 export function ProfilesSection() {
   const {
@@ -23,6 +27,47 @@ export function ProfilesSection() {
   } = useProfileStore();
 
   const hasProfile = (selectedProfile ?? defaultProfile) != null;
+  const activeProfile = selectedProfile ?? defaultProfile;
+  const [protection, setProtection] = useState<ProfileProtection>({ protected: false, read_only: false });
+  const [access, setAccess] = useState<WriteAccess | null>(null);
+  const [sessionReadOnly, setSessionReadOnly] = useState(false);
+  const [policyFile, setPolicyFile] = useState("");
+
+  useEffect(() => {
+    api.loadPolicyFilePath().then((p) => setPolicyFile(p ?? "")).catch(() => {});
+  }, []);
+
+  const savePolicyFile = async () => {
+    const path = policyFile.trim();
+    if (await api.savePolicyFilePath(path || null)) {
+      useLogsStore.getState().pushInfo(path ? `Team policy: ${path}` : "Team policy file cleared");
+    }
+  };
+
+  const refreshAccess = () => {
+    if (!activeProfile) return;
+    api.loadProfileProtection(activeProfile).then(setProtection).catch(() => {});
+    api.getWriteAccess(activeProfile).then(setAccess).catch(() => {});
+    api.loadReadOnlyMode().then(setSessionReadOnly).catch(() => {});
+  };
+
+  useEffect(refreshAccess, [activeProfile]);
+
+  const updateProtection = async (change: Partial<ProfileProtection>, message: string) => {
+    if (!activeProfile) return;
+    const next = { ...protection, ...change };
+    if (await api.saveProfileProtection(activeProfile, next)) {
+      useLogsStore.getState().pushInfo(message);
+      refreshAccess();
+    }
+  };
+
+  const toggleSessionReadOnly = async (value: boolean) => {
+    if (await api.saveReadOnlyMode(value)) {
+      useLogsStore.getState().pushInfo(`Read-only mode ${value ? "on" : "off"} for all profiles`);
+      refreshAccess();
+    }
+  };
   const StatusIcon = ssoChecking
     ? AlarmClock
     : ssoValid === true
@@ -123,6 +168,60 @@ export function ProfilesSection() {
                 : "Re-check"}
             </Button>
           </div>
+          <label className="label cursor-pointer justify-start gap-2 py-1" title="Writes need the secret name typed again">
+            <input
+              type="checkbox"
+              className="checkbox checkbox-xs checkbox-warning"
+              checked={access?.protected ?? protection.protected}
+              disabled={!hasProfile || (access?.locked_by_team && access.protected)}
+              onChange={(e) =>
+                void updateProtection(
+                  { protected: e.target.checked },
+                  `Profile ${activeProfile} is ${e.target.checked ? "now protected" : "no longer protected"}`
+                )
+              }
+            />
+            <span className="label-text text-xs">Protected profile</span>
+          </label>
+          <label className="label cursor-pointer justify-start gap-2 py-1" title="Reject every write to this profile">
+            <input
+              type="checkbox"
+              className="checkbox checkbox-xs checkbox-error"
+              checked={protection.read_only}
+              disabled={!hasProfile}
+              onChange={(e) =>
+                void updateProtection(
+                  { read_only: e.target.checked },
+                  `Profile ${activeProfile} is ${e.target.checked ? "now read-only" : "writable again"}`
+                )
+              }
+            />
+            <span className="label-text text-xs">Read-only profile</span>
+          </label>
+          <label className="label cursor-pointer justify-start gap-2 py-1" title="Reject every write, in every profile">
+            <input
+              type="checkbox"
+              className="checkbox checkbox-xs checkbox-error"
+              checked={sessionReadOnly}
+              onChange={(e) => void toggleSessionReadOnly(e.target.checked)}
+            />
+            <span className="label-text text-xs">Read-only mode (all profiles)</span>
+          </label>
+          <label className="label py-1">
+            <span className="label-text text-xs">Team policy file (JSON/YAML)</span>
+          </label>
+          <Input
+            size="xs"
+            value={policyFile}
+            placeholder="/path/to/policy.yaml"
+            onChange={(e) => setPolicyFile(e.target.value)}
+            onBlur={() => void savePolicyFile()}
+          />
+          {access?.read_only_reason && (
+            <div className="badge badge-error badge-xs" title={access.read_only_reason}>
+              Read-only: {access.read_only_reason}
+            </div>
+          )}
         </div>
       )}
     </div>
@@ -5,4 +5,6 @@ export type EditorTab = {
   isBinary: boolean;
   isTooLarge?: boolean;
   binarySize?: number;
+  /** Version id of the loaded value, sent back on save to detect concurrent edits */
+  versionId?: string | null;
 };
@@ -0,0 +1,70 @@
+import { create } from "zustand";
+import { api, CommandError, ProtectedAction } from "../services/tauriApi";
+
+type PendingConfirmation = {
+  profile: string | null;
+  action: ProtectedAction;
+  secretId: string;
+  message: string;
+};
+
+type State = {
+  pending: PendingConfirmation | null;
+  error: string | null;
+  _resolve: ((token: string | null) => void) | null;
+};
+
+type Actions = {
+  requestToken: (pending: PendingConfirmation) => Promise<string | null>;
+  confirm: (typedName: string) => Promise<void>;
+  cancel: () => void;
+};
+
+export const useConfirmationStore = create<State & Actions>((set, get) => ({
+  pending: null,
+  error: null,
+  _resolve: null,
+
+  requestToken: (pending) =>
+    new Promise((resolve) => {
+      // A new request replaces one that is still open
+      get()._resolve?.(null);
+      set({ pending, error: null, _resolve: resolve });
+    }),
+  confirm: async (typedName) => {
+    const { pending, _resolve } = get();
+    if (!pending) return;
+    try {
+      const token = await api.prepareProtectedWrite(pending.profile, pending.action, pending.secretId, typedName);
+      set({ pending: null, error: null, _resolve: null });
+      _resolve?.(token);
+    } catch (error) {
+      const errorMsg = typeof error === 'string' ? error : (error as any)?.message ?? String(error);
+      set({ error: errorMsg });
+    }
+  },
+  cancel: () => {
+    get()._resolve?.(null);
+    set({ pending: null, error: null, _resolve: null });
+  },
+}));
+
+/**
+ * Run a write; if the profile is protected, ask for the typed secret name and retry once with the token.
+ */
+export async function withProtectedWrite<T>(run: (confirmationToken: string | null) => Promise<T>): Promise<T> {
+  try {
+    return await run(null);
+  } catch (error) {
+    const err = error as CommandError;
+    if (err?.kind !== "confirmation_required") throw error;
+    const token = await useConfirmationStore.getState().requestToken({
+      profile: err.profile,
+      action: err.action,
+      secretId: err.secret_id,
+      message: err.message,
+    });
+    if (!token) throw new Error(`Cancelled ${err.action} of ${err.secret_id}`);
+    return run(token);
+  }
+}
@@ -1,11 +1,12 @@
 import { create } from "zustand";
 import { listen } from "@tauri-apps/api/event";
-import { api, SecretContent } from "../services/tauriApi";
+import { api, CommandError, SecretContent } from "../services/tauriApi";
 import type { EditorTab } from "../shared/types";
 import { useLogsStore } from "./useLogsStore";
 import { useSecretsListStore } from "./useSecretsListStore";
 import { useBookmarksStore } from "./useBookmarksStore";
 import { useProfileStore } from "./useProfileStore";
+import { withProtectedWrite } from "./useConfirmationStore";
 
 type State = {
   // editor
@@ -15,6 +16,8 @@ type State = {
   isCreatingNew: boolean;
   isBinary: boolean;
   importedBinary: { name: string; size: number; base64: string } | null;
+  versionId: string | null;
+  versionConflict: { profile: string | null; secretId: string; current: SecretContent } | null;
 
   // loading
   isFetchingSecret: boolean;
@@ -40,12 +43,13 @@ type Actions = {
   setIsBinary: (v: boolean) => void;
   setImportedBinary: (p: { name: string; size: number; base64: string } | null) => void;
   save: (profile: string | null) => Promise<void>;
+  resolveVersionConflict: (choice: "reload" | "overwrite" | "cancel") => Promise<void>;
   cancelEdit: () => void;
   setSecretId: (v: string) => void;
   _computeBase64Size: (b64: string) => number;
 
   // tabs
-  openTab: (secretId: string, content: string, isBinary: boolean, meta?: { isTooLarge?: boolean; binarySize?: number }) => string;
+  openTab: (secretId: string, content: string, isBinary: boolean, meta?: { isTooLarge?: boolean; binarySize?: number; versionId?: string | null }) => string;
   closeTab: (tabId: string) => void;
   closeOtherTabs: (tabId: string) => void;
   switchTab: (tabId: string) => void;
@@ -61,6 +65,8 @@ export const useEditorStore = create<State & Actions>((set, get) => ({
   isCreatingNew: false,
   isBinary: false,
   importedBinary: null,
+  versionId: null,
+  versionConflict: null,
 
   isFetchingSecret: false,
   fetchingSecretId: null,
@@ -114,6 +120,7 @@ export const useEditorStore = create<State & Actions>((set, get) => ({
       await new Promise(resolve => setTimeout(resolve, 0));
 
       const st2 = get();
+      const versionId = content.version_id ?? null;
       let parsedContent = "";
       let isBinary = false;
       let didOpenTab = false;
@@ -140,12 +147,13 @@ export const useEditorStore = create<State & Actions>((set, get) => ({
         if (sizeBytes > 50 * 1024) {
           parsedContent = "";
           set({ fetchedBinaryTooLarge: { name: secret_id, size: sizeBytes } });
-          const tabId = st2.openTab(secret_id, parsedContent, isBinary, { isTooLarge: true, binarySize: sizeBytes });
+          const tabId = st2.openTab(secret_id, parsedContent, isBinary, { isTooLarge: true, binarySize: sizeBytes, versionId });
           set({
             activeTabId: tabId,
             secretId: secret_id,
             editorContent: parsedContent,
             isBinary: isBinary,
+            versionId,
             isEditing: false,
             isCreatingNew: false,
             isFetchingSecret: false,
@@ -155,12 +163,13 @@ export const useEditorStore = create<State & Actions>((set, get) => ({
         } else {
           parsedContent = b64;
           set({ fetchedBinaryTooLarge: null });
-          const tabId = st2.openTab(secret_id, parsedContent, isBinary, { isTooLarge: false, binarySize: sizeBytes });
+          const tabId = st2.openTab(secret_id, parsedContent, isBinary, { isTooLarge: false, binarySize: sizeBytes, versionId });
           set({
             activeTabId: tabId,
             secretId: secret_id,
             editorContent: parsedContent,
             isBinary: isBinary,
+            versionId,
             isEditing: false,
             isCreatingNew: false,
             isFetchingSecret: false,
@@ -175,12 +184,13 @@ export const useEditorStore = create<State & Actions>((set, get) => ({
       }
 
       if (!didOpenTab) {
-        const tabId = st2.openTab(secret_id, parsedContent, isBinary, { isTooLarge: false });
+        const tabId = st2.openTab(secret_id, parsedContent, isBinary, { isTooLarge: false, versionId });
         set({
           activeTabId: tabId,
           secretId: secret_id,
           editorContent: parsedContent,
           isBinary: isBinary,
+          versionId,
           isEditing: false,
           isCreatingNew: false,
           isFetchingSecret: false,
@@ -258,7 +268,7 @@ export const useEditorStore = create<State & Actions>((set, get) => ({
     
     // Set content mặc định là JSON với cursor ở giữa dấu ngoặc kép
     const defaultContent = '{\n  ""\n}';
-    set({ isCreatingNew: true, isEditing: true, editorContent: defaultContent, secretId: "", importedBinary: null, isBinary: false });
+    set({ isCreatingNew: true, isEditing: true, editorContent: defaultContent, secretId: "", importedBinary: null, isBinary: false, versionId: null });
     pushInfo("Switched to create new secret mode");
   },
 
@@ -269,16 +279,20 @@ export const useEditorStore = create<State & Actions>((set, get) => ({
     pushInfo((st.isCreatingNew ? "Creating" : "Updating") + ` secret: ${st.secretId}`);
     
     try {
+      // The write created a new version; track it so the next save is checked against it
+      let versionId: string | null;
       if (st.isCreatingNew) {
         const payload = st.isBinary ? (st.importedBinary?.base64 ?? st.editorContent) : st.editorContent;
-        await api.createSecret(profile, st.secretId, payload, null, st.isBinary);
+        const created = await withProtectedWrite((token) => api.createSecret(profile, st.secretId, payload, null, st.isBinary, null, null, token));
+        versionId = created.version_id;
         pushSuccess("Created secret");
         
         // Force reload secrets list after creating new secret
         await listSecrets(profile, true);
       } else {
         const payload = st.isBinary ? (st.importedBinary?.base64 ?? st.editorContent) : st.editorContent;
-        await api.updateSecret(profile, st.secretId, payload, null, st.isBinary);
+        const updated = await withProtectedWrite((token) => api.updateSecret(profile, st.secretId, payload, null, st.isBinary, st.versionId, null, token));
+        versionId = updated.version_id;
         pushSuccess("Updated secret");
       }
 
@@ -315,11 +329,12 @@ export const useEditorStore = create<State & Actions>((set, get) => ({
           st.secretId, 
           tabContent, 
           st.isBinary,
-          tooLarge ? { isTooLarge: true, binarySize: tooLarge.size } : { isTooLarge: false }
+          tooLarge ? { isTooLarge: true, binarySize: tooLarge.size, versionId } : { isTooLarge: false, versionId }
         );
         set({ 
           activeTabId: tabId,
           editorContent: tabContent,
+          versionId,
           fetchedBinaryTooLarge: tooLarge,
         });
       } else if (st.activeTabId) {
@@ -332,12 +347,14 @@ export const useEditorStore = create<State & Actions>((set, get) => ({
                 isBinary: st.isBinary,
                 isTooLarge: tooLarge ? true : false,
                 binarySize: tooLarge ? tooLarge.size : undefined,
+                versionId,
               } 
             : t
         );
         set({ 
           tabs: updatedTabs,
           editorContent: tabContent,
+          versionId,
           fetchedBinaryTooLarge: tooLarge,
         });
       }
@@ -346,10 +363,60 @@ export const useEditorStore = create<State & Actions>((set, get) => ({
     } catch (error) {
       const errorMsg = typeof error === 'string' ? error : (error as any)?.message ?? String(error);
       pushError(`Failed to ${st.isCreatingNew ? 'create' : 'update'} secret: ${errorMsg}`);
+      if ((error as CommandError)?.kind === "version_conflict") {
+        // Let the user pick between the latest value and their own edits
+        const { current } = error as Extract<CommandError, { kind: "version_conflict" }>;
+        set({ versionConflict: { profile, secretId: st.secretId, current } });
+      }
+      if ((error as CommandError)?.kind === "policy_violation") {
+        for (const v of (error as Extract<CommandError, { kind: "policy_violation" }>).violations) {
+          pushError(`  [${v.rule}]${v.path ? ` ${v.path}:` : ""} ${v.message}`);
+        }
+      }
       // Không reset editing state khi có lỗi để user có thể sửa và thử lại
     }
   },
 
+  resolveVersionConflict: async (choice) => {
+    const st = get();
+    const conflict = st.versionConflict;
+    if (!conflict) return;
+    set({ versionConflict: null });
+    if (choice === "cancel") return;
+    const { pushInfo } = useLogsStore.getState();
+    const versionId = conflict.current.version_id ?? null;
+
+    if (choice === "overwrite") {
+      // Save again against the version that is current now
+      set({ versionId });
+      pushInfo(`Overwriting latest version of ${conflict.secretId}`);
+      await get().save(conflict.profile);
+      return;
+    }
+
+    const isBinary = !conflict.current.string && !!conflict.current.binary_base64;
+    let content = conflict.current.string ?? conflict.current.binary_base64 ?? "";
+    if (conflict.current.string) {
+      try {
+        content = JSON.stringify(JSON.parse(conflict.current.string), null, 2);
+      } catch {
+        // Not JSON; keep the raw text
+      }
+    }
+    const updatedTabs = st.tabs.map(t =>
+      t.id === st.activeTabId ? { ...t, content, isBinary, versionId } : t
+    );
+    set({
+      tabs: updatedTabs,
+      editorContent: content,
+      isBinary,
+      versionId,
+      isEditing: false,
+      importedBinary: null,
+    });
+    pushInfo(`Reloaded latest version of ${conflict.secretId}; your edits were discarded`);
+  },
+
   cancelEdit: () => {
     const st = get();
     const { pushInfo } = useLogsStore.getState();
@@ -372,6 +439,7 @@ export const useEditorStore = create<State & Actions>((set, get) => ({
           editorContent: tab.content,
           secretId: tab.secretId,
           isBinary: tab.isBinary,
+          versionId: tab.versionId ?? null,
           isEditing: false,
           isCreatingNew: false,
           importedBinary: null,
@@ -384,10 +452,10 @@ export const useEditorStore = create<State & Actions>((set, get) => ({
     pushInfo("Edit mode cancelled");
   },
 
-  openTab: (secretId: string, content: string, isBinary: boolean, meta?: { isTooLarge?: boolean; binarySize?: number }) => {
+  openTab: (secretId: string, content: string, isBinary: boolean, meta?: { isTooLarge?: boolean; binarySize?: number; versionId?: string | null }) => {
     const st = get();
     const tabId = `tab_${Date.now()}_${Math.random().toString(36).substr(2, 9)}`;
-    const newTab: EditorTab = { id: tabId, secretId, content, isBinary, isTooLarge: meta?.isTooLarge, binarySize: meta?.binarySize };
+    const newTab: EditorTab = { id: tabId, secretId, content, isBinary, isTooLarge: meta?.isTooLarge, binarySize: meta?.binarySize, versionId: meta?.versionId ?? null };
     set({ tabs: [...st.tabs, newTab], activeTabId: tabId });
     return tabId;
   },
@@ -428,6 +496,7 @@ export const useEditorStore = create<State & Actions>((set, get) => ({
       secretId: activeTab?.secretId ?? "",
       editorContent: activeTab?.content ?? "",
       isBinary: activeTab?.isBinary ?? false,
+      versionId: activeTab?.versionId ?? null,
       isEditing: false,
       isCreatingNew: false,
       fetchedBinaryTooLarge: tooLarge,
@@ -446,6 +515,7 @@ export const useEditorStore = create<State & Actions>((set, get) => ({
       secretId: tabToKeep.secretId,
       editorContent: tabToKeep.content,
       isBinary: tabToKeep.isBinary,
+      versionId: tabToKeep.versionId ?? null,
       isEditing: false,
       isCreatingNew: false,
     });
@@ -484,6 +554,7 @@ export const useEditorStore = create<State & Actions>((set, get) => ({
         secretId: tab.secretId,
         editorContent: tab.content,
         isBinary: tab.isBinary,
+        versionId: tab.versionId ?? null,
         isEditing: false,
         isCreatingNew: false,
         fetchedBinaryTooLarge: tooLarge,